@@ -0,0 +1,60 @@
+use std::hint::black_box;
+
+use advent_of_code_2022::{
+    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day15::Day15,
+    day16::Day16, day17::Day17, day18::Day18, day19::Day19, day2::Day2, day20::Day20, day21::Day21,
+    day22::Day22, day23::Day23, day24::Day24, day25::Day25, day3::Day3, day4::Day4, day5::Day5,
+    day6::Day6, day7::Day7, day8::Day8, day9::Day9, runner, solution::Solution,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Benchmarks the generator and both parts of a day separately, on its real input.
+/// Names are `dayN/parse`, `dayN/part1` and `dayN/part2` so results line up across commits.
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let path = runner::input_path(S::DAY);
+    let input = match runner::read_input(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("skipping day {}: {}", S::DAY, e);
+            return;
+        }
+    };
+    let parsed = S::parse(&input).expect("real input parses");
+
+    let mut group = c.benchmark_group(format!("day{}", S::DAY));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<Day1>(c);
+    bench_day::<Day2>(c);
+    bench_day::<Day3>(c);
+    bench_day::<Day4>(c);
+    bench_day::<Day5>(c);
+    bench_day::<Day6>(c);
+    bench_day::<Day7>(c);
+    bench_day::<Day8>(c);
+    bench_day::<Day9>(c);
+    bench_day::<Day10>(c);
+    bench_day::<Day11>(c);
+    bench_day::<Day12>(c);
+    bench_day::<Day13>(c);
+    bench_day::<Day14>(c);
+    bench_day::<Day15>(c);
+    bench_day::<Day16>(c);
+    bench_day::<Day17>(c);
+    bench_day::<Day18>(c);
+    bench_day::<Day19>(c);
+    bench_day::<Day20>(c);
+    bench_day::<Day21>(c);
+    bench_day::<Day22>(c);
+    bench_day::<Day23>(c);
+    bench_day::<Day24>(c);
+    bench_day::<Day25>(c);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
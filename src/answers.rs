@@ -0,0 +1,139 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::runner::{self, Part, RunError};
+
+#[derive(Debug, Default, Deserialize)]
+struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Result of checking a solution against the answers manifest.
+#[derive(Debug)]
+pub enum Verdict {
+    Correct,
+    Incorrect {
+        expected: String,
+        actual: String,
+    },
+    /// The solution ran but there's nothing recorded to compare it with.
+    Unrecorded(String),
+    Failed(RunError),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Incorrect { .. } | Verdict::Failed(_))
+    }
+}
+
+/// Known-good answers, keyed by the input file's stem (`day5`, `day5_test`, ...).
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<String, Expected>);
+
+/// Default location of the answers manifest.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input/2022/answers.toml")
+}
+
+/// Key an input file's answers are stored under.
+pub fn input_key(input: &Path) -> String {
+    input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, RunError> {
+        let manifest =
+            fs::read_to_string(path).map_err(|e| RunError::Input(path.to_path_buf(), e))?;
+        Self::parse(&manifest)
+    }
+
+    pub fn parse(manifest: &str) -> Result<Self, RunError> {
+        let answers = toml::from_str(manifest)
+            .map_err(|e| RunError::InvalidAnswers(e.message().to_string()))?;
+        Ok(Self(answers))
+    }
+
+    /// Keys of every input with recorded answers.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn expected(&self, key: &str, part: Part) -> Option<&str> {
+        let expected = self.0.get(key)?;
+        match part {
+            Part::One => expected.part1.as_deref(),
+            Part::Two => expected.part2.as_deref(),
+        }
+    }
+
+    /// `None` when there's no recorded answer to compare against.
+    pub fn check(&self, key: &str, part: Part, answer: &str) -> Option<bool> {
+        self.expected(key, part)
+            .map(|expected| normalize(expected) == normalize(answer))
+    }
+
+    /// Solves one part of a day against `input` and compares it with the recorded answer.
+    pub fn verify(&self, day: u8, part: Part, input: &Path) -> Verdict {
+        let answer = match runner::read_input(input).and_then(|i| runner::solve(day, part, &i)) {
+            Ok(answer) => answer,
+            Err(e) => return Verdict::Failed(e),
+        };
+
+        let key = input_key(input);
+        match self.check(&key, part, &answer) {
+            Some(true) => Verdict::Correct,
+            Some(false) => Verdict::Incorrect {
+                expected: self.expected(&key, part).unwrap_or_default().to_string(),
+                actual: answer,
+            },
+            None => Verdict::Unrecorded(answer),
+        }
+    }
+}
+
+// Multi-line answers (like the day 10 CRT) are easier to write in TOML without
+// their surrounding blank lines, so those are ignored when comparing.
+fn normalize(answer: &str) -> &str {
+    answer.trim_matches('\n')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const MANIFEST: &str = r#"
+[day5_test]
+part1 = "CMZ"
+
+[day10]
+part2 = """
+##
+ #"""
+"#;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(MANIFEST).unwrap();
+        assert_eq!(Some(true), answers.check("day5_test", Part::One, "CMZ"));
+        assert_eq!(Some(false), answers.check("day5_test", Part::One, "MCD"));
+        assert_eq!(None, answers.check("day5_test", Part::Two, "MCD"));
+        assert_eq!(Some(true), answers.check("day10", Part::Two, "\n##\n #"));
+    }
+
+    #[test]
+    fn test_input_key() {
+        assert_eq!(
+            "day5_test",
+            input_key(Path::new("input/2022/day5_test.txt"))
+        );
+    }
+}
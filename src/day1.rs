@@ -1,31 +1,30 @@
 use std::str::FromStr;
 
+use crate::error::{parse_blocks, parse_number, ParseError};
+use crate::solution::solution;
+
 #[derive(Debug)]
-struct Elf(Vec<i32>);
+pub struct Elf(Vec<i32>);
 
 impl FromStr for Elf {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let calories: Vec<i32> = s
+        let calories = s
             .lines()
-            .map(|i| i.trim().parse::<i32>().unwrap())
-            .collect();
+            .enumerate()
+            .map(|(index, line)| {
+                parse_number::<i32>(line, line.trim()).map_err(|e| e.on_line(index + 1))
+            })
+            .collect::<Result<Vec<i32>, ParseError>>()?;
 
         Ok(Elf(calories))
     }
 }
 
 #[aoc_generator(day1)]
-fn input_generator(input: &str) -> Vec<Elf> {
-    let elves: Vec<&str> = input.split("\n\n").collect();
-
-    let elves_and_calories = elves
-        .iter()
-        .map(|elf| Elf::from_str(elf).unwrap())
-        .collect();
-
-    elves_and_calories
+fn input_generator(input: &str) -> Result<Vec<Elf>, ParseError> {
+    parse_blocks(1, input)
 }
 
 #[aoc(day1, part1)]
@@ -42,6 +41,8 @@ fn part2(input: &[Elf]) -> i32 {
     calories[..=2].iter().sum()
 }
 
+solution!(Day1, 1, "Calorie Counting", Vec<Elf>, i32, i32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,13 +63,20 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(24000, part1(&parsed_input))
     }
 
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(45000, part2(&parsed_input))
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator("1000\n\n2000\n3o00").unwrap_err();
+        assert_eq!((1, 4, 1), (error.day, error.line, error.column));
+        assert_eq!("3o00", error.text);
+    }
 }
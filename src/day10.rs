@@ -1,20 +1,32 @@
 use std::str::FromStr;
 
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Addx(i32),
     Noop,
 }
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.starts_with("addx") {
-            let (_, amount) = s.split_once(' ').unwrap();
-            Ok(Self::Addx(amount.parse::<i32>().unwrap()))
-        } else {
-            Ok(Self::Noop)
+        let trimmed = s.trim();
+        match trimmed.split_once(' ') {
+            Some(("addx", amount)) => Ok(Self::Addx(parse_number(s, amount)?)),
+            None if trimmed == "noop" => Ok(Self::Noop),
+            None if trimmed == "addx" => Err(ParseError::at_token(
+                ParseErrorKind::MissingToken,
+                s,
+                &trimmed[trimmed.len()..],
+            )),
+            _ => Err(ParseError::at_token(
+                ParseErrorKind::UnknownToken,
+                s,
+                trimmed,
+            )),
         }
     }
 }
@@ -105,7 +117,6 @@ impl Cpu {
             .chars()
             .collect::<Vec<char>>()
             .chunks(40)
-            .into_iter()
             .map(|row| row.iter().collect::<String>())
             .collect::<Vec<String>>()
             .join("\n")
@@ -113,11 +124,8 @@ impl Cpu {
 }
 
 #[aoc_generator(day10)]
-fn input_generator(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .map(|line| Instruction::from_str(line.trim()).unwrap())
-        .collect()
+fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(10, input)
 }
 
 #[aoc(day10, part1)]
@@ -140,6 +148,9 @@ fn part2(instructions: &[Instruction]) -> String {
     format!("\n{}", screen)
 }
 
+solution!(Day10, 10, "Cathode-Ray Tube", Vec<Instruction>, i32, String);
+
+#[cfg(test)]
 mod tests {
     use super::*;
     const INPUT: &str = "addx 15
@@ -291,7 +302,7 @@ noop";
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(13140, part1(&parsed_input))
     }
 
@@ -304,7 +315,14 @@ noop";
 #######       #######       #######     ";
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(PART2_OUTPUT, part2(&parsed_input))
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator("noop\naddx 3\naddx -x").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumber, error.kind);
+        assert_eq!((10, 3, 6), (error.day, error.line, error.column));
+    }
 }
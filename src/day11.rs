@@ -0,0 +1,313 @@
+use std::str::FromStr;
+
+use crate::error::{blocks, parse_blocks, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Old,
+    Value(u64),
+}
+
+impl FromStr for Operand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "old" {
+            Ok(Self::Old)
+        } else {
+            Ok(Self::Value(parse_number(s, s)?))
+        }
+    }
+}
+
+impl Operand {
+    fn resolve(&self, old: u64) -> u64 {
+        match self {
+            Operand::Old => old,
+            Operand::Value(value) => *value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+/// The right hand side of `new = old * 19` and friends.
+#[derive(Debug, Clone, Copy)]
+struct Operation {
+    lhs: Operand,
+    operator: Operator,
+    rhs: Operand,
+}
+
+impl FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        let (lhs, operator, rhs) = match tokens[..] {
+            [lhs, operator, rhs] => (lhs, operator, rhs),
+            _ => return Err(ParseError::at_token(ParseErrorKind::MissingToken, s, s)),
+        };
+
+        let operator = match operator {
+            "+" => Operator::Add,
+            "*" => Operator::Multiply,
+            _ => {
+                return Err(ParseError::at_token(
+                    ParseErrorKind::UnknownToken,
+                    s,
+                    operator,
+                ))
+            }
+        };
+
+        let column = |token: &str| token.as_ptr() as usize - s.as_ptr() as usize;
+        Ok(Self {
+            lhs: Operand::from_str(lhs).map_err(|e| e.shift_column(column(lhs)))?,
+            operator,
+            rhs: Operand::from_str(rhs).map_err(|e| e.shift_column(column(rhs)))?,
+        })
+    }
+}
+
+impl Operation {
+    fn apply(&self, old: u64) -> u64 {
+        let (lhs, rhs) = (self.lhs.resolve(old), self.rhs.resolve(old));
+        match self.operator {
+            Operator::Add => lhs + rhs,
+            Operator::Multiply => lhs * rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().collect();
+
+        // returns what follows `prefix` on the given (0-based) line of the block,
+        // erroring with a 1-based line number relative to the block
+        let field = |index: usize, prefix: &str| -> Result<&str, ParseError> {
+            let line = lines.get(index).ok_or_else(|| {
+                ParseError::new(ParseErrorKind::MissingToken, prefix).on_line(index + 1)
+            })?;
+            line.trim_start().strip_prefix(prefix).ok_or_else(|| {
+                ParseError::at_token(ParseErrorKind::UnknownToken, line, line.trim_start())
+                    .on_line(index + 1)
+            })
+        };
+        let in_line = |index: usize| move |e: ParseError| e.on_line(index + 1);
+
+        field(0, "Monkey ")?;
+
+        let items_line = lines.get(1).copied().unwrap_or_default();
+        let items = field(1, "Starting items:")?
+            .split(',')
+            .map(|item| parse_number(items_line, item.trim()).map_err(in_line(1)))
+            .collect::<Result<Vec<u64>, ParseError>>()?;
+
+        let operation = field(2, "Operation: new =")?;
+        let operation_offset = lines[2].len() - operation.len();
+        let operation = Operation::from_str(operation)
+            .map_err(|e| e.shift_column(operation_offset).on_line(3))?;
+
+        let divisor_token = field(3, "Test: divisible by ")?;
+        let divisor = parse_number(lines[3], divisor_token).map_err(in_line(3))?;
+        if divisor == 0 {
+            return Err(ParseError::at_token(
+                ParseErrorKind::InvalidNumber,
+                lines[3],
+                divisor_token,
+            )
+            .on_line(4));
+        }
+        let if_true = field(4, "If true: throw to monkey ")?;
+        let if_true = parse_number(lines[4], if_true).map_err(in_line(4))?;
+        let if_false = field(5, "If false: throw to monkey ")?;
+        let if_false = parse_number(lines[5], if_false).map_err(in_line(5))?;
+
+        Ok(Self {
+            items,
+            operation,
+            divisor,
+            if_true,
+            if_false,
+        })
+    }
+}
+
+/// How worry levels are kept in check after each inspection.
+#[derive(Debug, Clone, Copy)]
+enum Relief {
+    /// Part 1: relief that the item wasn't damaged divides worry.
+    Divide(u64),
+    /// Part 2: no relief, but every monkey only cares about divisibility, so worry can be
+    /// reduced modulo the product of all their divisors without changing any decisions.
+    Modulo(u64),
+}
+
+impl Relief {
+    fn apply(&self, worry: u64) -> u64 {
+        match self {
+            Relief::Divide(by) => worry / by,
+            Relief::Modulo(by) => worry % by,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct KeepAway {
+    monkeys: Vec<Monkey>,
+    inspections: Vec<usize>,
+    relief: Relief,
+}
+
+impl KeepAway {
+    fn new(monkeys: &[Monkey], relief: Relief) -> Self {
+        Self {
+            monkeys: monkeys.to_vec(),
+            inspections: vec![0; monkeys.len()],
+            relief,
+        }
+    }
+
+    fn play_round(&mut self) {
+        for index in 0..self.monkeys.len() {
+            let items = std::mem::take(&mut self.monkeys[index].items);
+            self.inspections[index] += items.len();
+
+            for item in items {
+                let monkey = &self.monkeys[index];
+                let worry = self.relief.apply(monkey.operation.apply(item));
+                let target = if worry.is_multiple_of(monkey.divisor) {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                self.monkeys[target].items.push(worry);
+            }
+        }
+    }
+
+    fn monkey_business(&self) -> usize {
+        let mut inspections = self.inspections.clone();
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+        inspections.iter().take(2).product()
+    }
+}
+
+#[aoc_generator(day11)]
+fn input_generator(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let monkeys: Vec<Monkey> = parse_blocks(11, input)?;
+
+    // make sure nobody throws to a monkey that doesn't exist, pointing at the
+    // `If true`/`If false` line that does
+    for (monkey, (first_line, block)) in monkeys.iter().zip(blocks(input)) {
+        for (index, target) in [(4, monkey.if_true), (5, monkey.if_false)] {
+            if target >= monkeys.len() {
+                let line = block.lines().nth(index).unwrap_or(block);
+                let token = line.rsplit(' ').next().unwrap_or(line);
+                return Err(
+                    ParseError::at_token(ParseErrorKind::InvalidLayout, line, token)
+                        .in_day(11)
+                        .on_line(first_line + index),
+                );
+            }
+        }
+    }
+
+    Ok(monkeys)
+}
+
+#[aoc(day11, part1)]
+fn part1(monkeys: &[Monkey]) -> usize {
+    let mut game = KeepAway::new(monkeys, Relief::Divide(3));
+    for _ in 0..20 {
+        game.play_round();
+    }
+    game.monkey_business()
+}
+
+#[aoc(day11, part2)]
+fn part2(monkeys: &[Monkey]) -> usize {
+    let modulus = monkeys.iter().map(|m| m.divisor).product();
+    let mut game = KeepAway::new(monkeys, Relief::Modulo(modulus));
+    for _ in 0..10_000 {
+        game.play_round();
+    }
+    game.monkey_business()
+}
+
+solution!(Day11, 11, "Monkey in the Middle", Vec<Monkey>, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(10605, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(2713310158, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("new = old + 6", "new = old - 6");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!((11, 10, 24), (error.day, error.line, error.column));
+
+        let input = INPUT.replace("If false: throw to monkey 1", "If false: throw to monkey 4");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+        assert_eq!((11, 27, 31), (error.day, error.line, error.column));
+    }
+}
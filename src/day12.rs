@@ -0,0 +1,183 @@
+use std::{cmp::Ordering, collections::VecDeque};
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::{Grid, Position};
+use crate::solution::solution;
+
+#[derive(Debug)]
+pub struct HeightMap {
+    heights: Grid<u8>,
+    start: Position,
+    end: Position,
+}
+
+impl HeightMap {
+    /// Breadth-first search from `from` until `is_goal` matches, only taking steps that
+    /// `can_step(current_height, next_height)` allows. Returns the path, both ends included.
+    fn bfs(
+        &self,
+        from: Position,
+        can_step: impl Fn(u8, u8) -> bool,
+        is_goal: impl Fn(Position) -> bool,
+    ) -> Option<Vec<Position>> {
+        let mut came_from: Grid<Option<Position>> =
+            Grid::new(self.heights.rows(), self.heights.cols(), None);
+        let mut queue = VecDeque::from([from]);
+        came_from[from] = Some(from);
+
+        while let Some(position) = queue.pop_front() {
+            if is_goal(position) {
+                let mut path = vec![position];
+                let mut current = position;
+                while current != from {
+                    current = came_from[current].unwrap();
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for next in self.heights.neighbors4(position) {
+                if came_from[next].is_none() && can_step(self.heights[position], self.heights[next])
+                {
+                    came_from[next] = Some(position);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Shortest path from `S` to `E`, climbing at most one step at a time.
+    pub fn path_from_start(&self) -> Option<Vec<Position>> {
+        self.bfs(
+            self.start,
+            |current, next| next <= current + 1,
+            |position| position == self.end,
+        )
+    }
+
+    /// Shortest path from any square at the lowest elevation to `E`.
+    ///
+    /// Searches backwards from `E` (so every `a` is a potential goal at once) and flips
+    /// the path around afterwards.
+    pub fn path_from_lowest(&self) -> Option<Vec<Position>> {
+        let mut path = self.bfs(
+            self.end,
+            |current, next| current <= next + 1,
+            |position| self.heights[position] == 0,
+        )?;
+        path.reverse();
+        Some(path)
+    }
+
+    /// Draws `path` over the map the way the puzzle does: each square shows the
+    /// direction of the next step, `E` marks the end and everything else is `.`.
+    pub fn render_path(&self, path: &[Position]) -> String {
+        let mut screen: Grid<char> = Grid::new(self.heights.rows(), self.heights.cols(), '.');
+
+        for step in path.windows(2) {
+            let ((from_row, from_col), (to_row, to_col)) = (step[0], step[1]);
+            screen[step[0]] = match (to_row.cmp(&from_row), to_col.cmp(&from_col)) {
+                (Ordering::Less, _) => '^',
+                (Ordering::Greater, _) => 'v',
+                (_, Ordering::Less) => '<',
+                _ => '>',
+            };
+        }
+        if let Some(&last) = path.last() {
+            screen[last] = 'E';
+        }
+
+        screen.to_string()
+    }
+}
+
+#[aoc_generator(day12)]
+fn input_generator(input: &str) -> Result<HeightMap, ParseError> {
+    let markers = Grid::parse(input, |c| match c {
+        'a'..='z' | 'S' | 'E' => Ok(c),
+        _ => Err(ParseErrorKind::UnknownToken),
+    })
+    .map_err(|e| e.in_day(12))?;
+
+    let missing = |marker: &str| ParseError::new(ParseErrorKind::MissingToken, marker).in_day(12);
+    let start = markers.find(|&c| c == 'S').ok_or_else(|| missing("S"))?;
+    let end = markers.find(|&c| c == 'E').ok_or_else(|| missing("E"))?;
+
+    let heights = markers.map(|&c| match c {
+        'S' => 0,
+        'E' => b'z' - b'a',
+        c => c as u8 - b'a',
+    });
+
+    Ok(HeightMap {
+        heights,
+        start,
+        end,
+    })
+}
+
+#[aoc(day12, part1)]
+fn part1(map: &HeightMap) -> usize {
+    let path = map.path_from_start().expect("no path from S to E");
+    path.len() - 1
+}
+
+#[aoc(day12, part2)]
+fn part2(map: &HeightMap) -> usize {
+    let path = map.path_from_lowest().expect("no path from any a to E");
+    path.len() - 1
+}
+
+solution!(
+    Day12,
+    12,
+    "Hill Climbing Algorithm",
+    HeightMap,
+    usize,
+    usize
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(31, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(29, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_path_is_walkable() {
+        let map = input_generator(INPUT).unwrap();
+        let path = map.path_from_start().unwrap();
+
+        assert_eq!(Some(&map.start), path.first());
+        assert_eq!(Some(&map.end), path.last());
+        for step in path.windows(2) {
+            assert!(map.heights.neighbors4(step[0]).any(|p| p == step[1]));
+            assert!(map.heights[step[1]] <= map.heights[step[0]] + 1);
+        }
+    }
+
+    #[test]
+    fn test_render_path() {
+        let map = input_generator("Sbcdefghijklm\nEyxwvutsrqpon").unwrap();
+        let path = map.path_from_start().unwrap();
+        assert_eq!(">>>>>>>>>>>>v\nE<<<<<<<<<<<<", map.render_path(&path));
+    }
+}
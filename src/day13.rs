@@ -0,0 +1,289 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::error::{parse_blocks, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+/// Packets compare (and are equal) by the puzzle's ordering, so `2`, `[2]` and `[[2]]`
+/// are all equal even though they're written differently.
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(left), Packet::Integer(right)) => left.cmp(right),
+            (Packet::List(left), Packet::List(right)) => left.cmp(right),
+            (Packet::Integer(left), Packet::List(right)) => {
+                [Packet::Integer(*left)][..].cmp(&right[..])
+            }
+            (Packet::List(left), Packet::Integer(right)) => {
+                left[..].cmp(&[Packet::Integer(*right)][..])
+            }
+        }
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Integer(value) => write!(f, "{}", value),
+            Packet::List(packets) => {
+                write!(f, "[")?;
+                for (index, packet) in packets.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", packet)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Recursive descent over a single packet line, keeping track of where it is so errors
+/// can point at the offending character.
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        let rest = self.rest();
+        let token = rest.get(..rest.chars().next().map_or(0, char::len_utf8));
+        ParseError::at_token(kind, self.source, token.unwrap_or(rest))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += c.len_utf8();
+                Ok(())
+            }
+            Some(_) => Err(self.error(ParseErrorKind::UnknownToken)),
+            None => Err(self.error(ParseErrorKind::MissingToken)),
+        }
+    }
+
+    fn packet(&mut self) -> Result<Packet, ParseError> {
+        match self.peek() {
+            Some('[') => self.list(),
+            Some(c) if c.is_ascii_digit() => self.integer(),
+            Some(_) => Err(self.error(ParseErrorKind::UnknownToken)),
+            None => Err(self.error(ParseErrorKind::MissingToken)),
+        }
+    }
+
+    fn list(&mut self) -> Result<Packet, ParseError> {
+        self.expect('[')?;
+        let mut packets = vec![];
+
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Packet::List(packets));
+        }
+
+        loop {
+            packets.push(self.packet()?);
+            match self.peek() {
+                Some(',') => self.position += 1,
+                _ => break,
+            }
+        }
+        self.expect(']')?;
+
+        Ok(Packet::List(packets))
+    }
+
+    fn integer(&mut self) -> Result<Packet, ParseError> {
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value = parse_number(self.source, &rest[..length])?;
+        self.position += length;
+
+        Ok(Packet::Integer(value))
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            source: s,
+            position: 0,
+        };
+        let packet = parser.list()?;
+
+        if parser.position < s.len() {
+            return Err(parser.error(ParseErrorKind::UnknownToken));
+        }
+
+        Ok(packet)
+    }
+}
+
+#[derive(Debug)]
+pub struct Pair {
+    left: Packet,
+    right: Packet,
+}
+
+impl FromStr for Pair {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let mut packet = |line: usize| {
+            let text = lines
+                .next()
+                .ok_or_else(|| ParseError::new(ParseErrorKind::MissingToken, s).on_line(line))?;
+            Packet::from_str(text).map_err(|e| e.on_line(line))
+        };
+
+        let left = packet(1)?;
+        let right = packet(2)?;
+        if let Some(extra) = lines.next() {
+            return Err(ParseError::new(ParseErrorKind::InvalidLayout, extra).on_line(3));
+        }
+
+        Ok(Self { left, right })
+    }
+}
+
+fn divider(value: u32) -> Packet {
+    Packet::List(vec![Packet::List(vec![Packet::Integer(value)])])
+}
+
+#[aoc_generator(day13)]
+fn input_generator(input: &str) -> Result<Vec<Pair>, ParseError> {
+    parse_blocks(13, input)
+}
+
+#[aoc(day13, part1)]
+fn part1(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, pair)| pair.left < pair.right)
+        .map(|(index, _)| index + 1)
+        .sum()
+}
+
+#[aoc(day13, part2)]
+fn part2(pairs: &[Pair]) -> usize {
+    let packets: Vec<&Packet> = pairs
+        .iter()
+        .flat_map(|pair| [&pair.left, &pair.right])
+        .collect();
+
+    // no need to sort everything, a divider's index is just how many packets come before it
+    let (first, second) = (divider(2), divider(6));
+    let first_index = packets.iter().filter(|&&p| *p < first).count() + 1;
+    let second_index = packets.iter().filter(|&&p| *p < second).count() + 2;
+
+    first_index * second_index
+}
+
+solution!(Day13, 13, "Distress Signal", Vec<Pair>, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(13, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(140, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for line in INPUT.lines().filter(|line| !line.is_empty()) {
+            assert_eq!(line, Packet::from_str(line).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_equality_matches_ordering() {
+        let integer = Packet::Integer(2);
+        let list = Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]);
+        assert_eq!(Ordering::Equal, integer.cmp(&list));
+        assert_eq!(integer, list);
+        assert_ne!(
+            integer,
+            Packet::List(vec![Packet::Integer(2), Packet::Integer(2)])
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("[[8,7,6]]", "[[8,7;6]]");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!((13, 8, 6), (error.day, error.line, error.column));
+
+        let error = Packet::from_str("[1,[2]").unwrap_err();
+        assert_eq!(
+            (ParseErrorKind::MissingToken, 7),
+            (error.kind, error.column)
+        );
+    }
+}
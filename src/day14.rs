@@ -0,0 +1,291 @@
+use std::{fmt, str::FromStr};
+
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::grid::{Grid, Position};
+use crate::solution::solution;
+
+/// Where the sand comes from, as `(x, y)`.
+const SOURCE: (usize, usize) = (500, 0);
+
+/// One line of the scan: rock along straight segments between `(x, y)` points.
+#[derive(Debug)]
+pub struct Path {
+    points: Vec<(usize, usize)>,
+}
+
+impl FromStr for Path {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut points: Vec<(usize, usize)> = vec![];
+
+        for token in s.split(" -> ") {
+            let (x, y) = token
+                .split_once(',')
+                .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, token))?;
+            let point = (parse_number(s, x)?, parse_number(s, y)?);
+
+            // the cave is drawn with horizontal and vertical lines only
+            if let Some(&(last_x, last_y)) = points.last() {
+                if last_x != point.0 && last_y != point.1 {
+                    return Err(ParseError::at_token(
+                        ParseErrorKind::InvalidLayout,
+                        s,
+                        token,
+                    ));
+                }
+            }
+            points.push(point);
+        }
+
+        Ok(Self { points })
+    }
+}
+
+impl Path {
+    /// Every `(x, y)` covered by the path, corners included.
+    fn tiles(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // a path with a single point has no segments, but is still a rock
+        let lone_point = match self.points[..] {
+            [point] => Some(point),
+            _ => None,
+        };
+
+        self.points
+            .windows(2)
+            .flat_map(|segment| {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                (x1.min(x2)..=x1.max(x2))
+                    .flat_map(move |x| (y1.min(y2)..=y1.max(y2)).map(move |y| (x, y)))
+            })
+            .chain(lone_point)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Floor {
+    /// Part 1: sand below the lowest rock falls forever.
+    Abyss,
+    /// Part 2: there's an endless floor two below the lowest rock.
+    Solid,
+}
+
+/// The slice of the cave sand can actually reach.
+///
+/// Sand moves at most one column sideways per row it falls, so a grid as wide as it is
+/// tall on either side of the source is enough, even with an infinite floor. In a cave
+/// deeper than the source's x coordinate that reaches past x = 0.
+#[derive(Debug)]
+pub struct Cave {
+    tiles: Grid<Tile>,
+    /// x coordinate of the leftmost column of `tiles`, negative in very deep caves.
+    left: isize,
+    lowest_rock: usize,
+    floor: Floor,
+}
+
+impl Cave {
+    pub fn new(paths: &[Path], floor: Floor) -> Self {
+        let rocks: Vec<(usize, usize)> = paths.iter().flat_map(Path::tiles).collect();
+        let lowest_rock = rocks.iter().map(|&(_, y)| y).max().unwrap_or(0);
+
+        let rows = lowest_rock + 3;
+        let min_x = rocks.iter().map(|&(x, _)| x).min().unwrap_or(SOURCE.0);
+        let max_x = rocks.iter().map(|&(x, _)| x).max().unwrap_or(SOURCE.0);
+        let left = (min_x as isize).min(SOURCE.0 as isize - rows as isize);
+        let right = max_x.max(SOURCE.0 + rows) as isize;
+
+        let mut tiles = Grid::new(rows, (right - left + 1) as usize, Tile::Air);
+        for (x, y) in rocks {
+            tiles[(y, (x as isize - left) as usize)] = Tile::Rock;
+        }
+        if floor == Floor::Solid {
+            for col in 0..tiles.cols() {
+                tiles[(rows - 1, col)] = Tile::Rock;
+            }
+        }
+
+        Self {
+            tiles,
+            left,
+            lowest_rock,
+            floor,
+        }
+    }
+
+    /// Where the sand comes from, as a position in `tiles`.
+    fn source(&self) -> Position {
+        (SOURCE.1, (SOURCE.0 as isize - self.left) as usize)
+    }
+
+    /// Drops one unit of sand from the source and returns where it came to rest, or
+    /// `None` if it fell into the abyss or the source is already buried.
+    pub fn drop_sand(&mut self) -> Option<Position> {
+        let mut position = self.source();
+        if self.tiles[position] != Tile::Air {
+            return None;
+        }
+
+        loop {
+            let (row, col) = position;
+            if self.floor == Floor::Abyss && row > self.lowest_rock {
+                return None;
+            }
+
+            let next = [(row + 1, col), (row + 1, col - 1), (row + 1, col + 1)]
+                .into_iter()
+                .find(|&p| self.tiles[p] == Tile::Air);
+            match next {
+                Some(next) => position = next,
+                None => break,
+            }
+        }
+
+        self.tiles[position] = Tile::Sand;
+        Some(position)
+    }
+
+    /// Keeps pouring sand until it stops coming to rest, returning how many units did.
+    pub fn fill(&mut self) -> usize {
+        std::iter::from_fn(|| self.drop_sand()).count()
+    }
+
+    /// Draws the part of the cave that has rock or sand in it, with the source as `+`.
+    pub fn render(&self) -> String {
+        let occupied: Vec<Position> = self
+            .tiles
+            .iter()
+            .filter(|(_, &tile)| tile != Tile::Air)
+            .map(|(position, _)| position)
+            .collect();
+        let source = self.source();
+
+        let bottom = occupied.iter().map(|p| p.0).max().unwrap_or(0);
+        let first_col = occupied.iter().map(|p| p.1).min().unwrap_or(source.1);
+        let last_col = occupied.iter().map(|p| p.1).max().unwrap_or(source.1);
+
+        (0..=bottom)
+            .map(|row| {
+                (first_col.min(source.1)..=last_col.max(source.1))
+                    .map(|col| match self.tiles[(row, col)] {
+                        Tile::Air if (row, col) == source => "+".to_string(),
+                        tile => tile.to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[aoc_generator(day14)]
+fn input_generator(input: &str) -> Result<Vec<Path>, ParseError> {
+    parse_lines(14, input)
+}
+
+#[aoc(day14, part1)]
+fn part1(paths: &[Path]) -> usize {
+    Cave::new(paths, Floor::Abyss).fill()
+}
+
+#[aoc(day14, part2)]
+fn part2(paths: &[Path]) -> usize {
+    Cave::new(paths, Floor::Solid).fill()
+}
+
+solution!(Day14, 14, "Regolith Reservoir", Vec<Path>, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(24, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(93, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_render() {
+        let paths = input_generator(INPUT).unwrap();
+        let mut cave = Cave::new(&paths, Floor::Abyss);
+        assert_eq!(
+            "......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########.",
+            cave.render()
+        );
+
+        cave.fill();
+        assert_eq!(
+            "......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.",
+            cave.render()
+        );
+    }
+
+    #[test]
+    fn test_single_point_path() {
+        let paths = input_generator("500,4").unwrap();
+        assert_eq!("+\n.\n.\n.\n#", Cave::new(&paths, Floor::Abyss).render());
+        assert_eq!(35, part2(&paths));
+    }
+
+    #[test]
+    fn test_deep_cave() {
+        // a staircase of single rocks leading the first unit of sand down and left to
+        // x = 0, in a cave deeper than the source is far from there
+        let input: Vec<String> = (0..500).map(|k| format!("{},{}", 500 - k, k + 1)).collect();
+        let paths = input_generator(&input.join("\n")).unwrap();
+        let mut cave = Cave::new(&paths, Floor::Solid);
+        assert_eq!(Some((501, -cave.left as usize)), cave.drop_sand());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator("498,4 -> 498,6\n503,4 -> 502,5").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+        assert_eq!((14, 2, 10), (error.day, error.line, error.column));
+    }
+}
@@ -0,0 +1,191 @@
+use std::str::FromStr;
+
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+/// Row checked in part 1 (the example uses 10).
+const ROW: i64 = 2_000_000;
+/// Largest coordinate the distress beacon can have in part 2 (the example uses 20).
+const SEARCH_MAX: i64 = 4_000_000;
+
+type Point = (i64, i64);
+
+fn manhattan((x1, y1): Point, (x2, y2): Point) -> i64 {
+    (x1 - x2).abs() + (y1 - y2).abs()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sensor {
+    position: Point,
+    beacon: Point,
+    /// Distance to the closest beacon; nothing within it can be another beacon.
+    radius: i64,
+}
+
+/// Parses `x=2, y=18` out of `source`.
+fn parse_point(source: &str, text: &str) -> Result<Point, ParseError> {
+    let (x, y) = text
+        .split_once(", ")
+        .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, source, text))?;
+    let coordinate = |token: &str, prefix: &str| {
+        let value = token
+            .strip_prefix(prefix)
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::UnknownToken, source, token))?;
+        parse_number(source, value)
+    };
+
+    Ok((coordinate(x, "x=")?, coordinate(y, "y=")?))
+}
+
+impl FromStr for Sensor {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s
+            .strip_prefix("Sensor at ")
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::UnknownToken, s, s))?;
+        let (position, beacon) = rest
+            .split_once(": closest beacon is at ")
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, rest))?;
+
+        let position = parse_point(s, position)?;
+        let beacon = parse_point(s, beacon)?;
+
+        Ok(Self {
+            position,
+            beacon,
+            radius: manhattan(position, beacon),
+        })
+    }
+}
+
+impl Sensor {
+    /// Inclusive range of x coordinates this sensor covers on row `y`, if any.
+    fn coverage(&self, y: i64) -> Option<(i64, i64)> {
+        let reach = self.radius - (self.position.1 - y).abs();
+        (reach >= 0).then_some((self.position.0 - reach, self.position.0 + reach))
+    }
+}
+
+/// Fills `intervals` with the sorted, non-overlapping ranges covered on row `y`.
+/// Adjacent ranges are merged too, since coordinates are whole numbers.
+fn covered_intervals(sensors: &[Sensor], y: i64, intervals: &mut Vec<(i64, i64)>) {
+    intervals.clear();
+    intervals.extend(sensors.iter().filter_map(|sensor| sensor.coverage(y)));
+    intervals.sort_unstable();
+
+    let mut merged = 0;
+    for index in 0..intervals.len() {
+        let (start, end) = intervals[index];
+        if merged > 0 && start <= intervals[merged - 1].1 + 1 {
+            intervals[merged - 1].1 = intervals[merged - 1].1.max(end);
+        } else {
+            intervals[merged] = (start, end);
+            merged += 1;
+        }
+    }
+    intervals.truncate(merged);
+}
+
+/// Positions on row `y` that can't hold a beacon.
+fn excluded_in_row(sensors: &[Sensor], y: i64) -> usize {
+    let mut intervals = vec![];
+    covered_intervals(sensors, y, &mut intervals);
+
+    let covered: i64 = intervals.iter().map(|(start, end)| end - start + 1).sum();
+    let mut beacons: Vec<i64> = sensors
+        .iter()
+        .filter(|sensor| sensor.beacon.1 == y)
+        .map(|sensor| sensor.beacon.0)
+        .collect();
+    beacons.sort_unstable();
+    beacons.dedup();
+
+    covered as usize - beacons.len()
+}
+
+/// The only position with both coordinates in `0..=max` that no sensor covers.
+fn find_distress_beacon(sensors: &[Sensor], max: i64) -> Option<Point> {
+    let mut intervals = vec![];
+
+    for y in 0..=max {
+        covered_intervals(sensors, y, &mut intervals);
+
+        let mut x = 0;
+        for &(start, end) in &intervals {
+            if start > x {
+                break;
+            }
+            x = x.max(end + 1);
+        }
+        if x <= max {
+            return Some((x, y));
+        }
+    }
+
+    None
+}
+
+fn tuning_frequency((x, y): Point) -> i64 {
+    x * 4_000_000 + y
+}
+
+#[aoc_generator(day15)]
+fn input_generator(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    parse_lines(15, input)
+}
+
+#[aoc(day15, part1)]
+fn part1(sensors: &[Sensor]) -> usize {
+    excluded_in_row(sensors, ROW)
+}
+
+#[aoc(day15, part2)]
+fn part2(sensors: &[Sensor]) -> i64 {
+    let beacon =
+        find_distress_beacon(sensors, SEARCH_MAX).expect("no room for the distress beacon");
+    tuning_frequency(beacon)
+}
+
+solution!(Day15, 15, "Beacon Exclusion Zone", Vec<Sensor>, usize, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(26, excluded_in_row(&parsed_input, 10))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        let beacon = find_distress_beacon(&parsed_input, 20).unwrap();
+        assert_eq!((14, 11), beacon);
+        assert_eq!(56000011, tuning_frequency(beacon))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("x=12, y=14", "x=12, z=14");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!((15, 4, 17), (error.day, error.line, error.column));
+    }
+}
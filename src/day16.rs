@@ -0,0 +1,216 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+const START: &str = "AA";
+/// Both parts keep one entry per set of open valves, so this bounds memory at 2^20 entries.
+const MAX_USEFUL_VALVES: usize = 20;
+
+#[derive(Debug, Clone)]
+struct Valve {
+    name: String,
+    flow_rate: u32,
+    tunnels: Vec<String>,
+}
+
+impl FromStr for Valve {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s
+            .strip_prefix("Valve ")
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::UnknownToken, s, s))?;
+        let (name, rest) = rest
+            .split_once(" has flow rate=")
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, rest))?;
+        let (flow_rate, rest) = rest
+            .split_once("; ")
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, rest))?;
+
+        // the puzzle minds its grammar: "tunnel leads to valve" when there's only one
+        let tunnels = ["tunnels lead to valves ", "tunnel leads to valve "]
+            .iter()
+            .find_map(|prefix| rest.strip_prefix(prefix))
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::UnknownToken, s, rest))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            flow_rate: parse_number(s, flow_rate)?,
+            tunnels: tunnels.split(", ").map(str::to_string).collect(),
+        })
+    }
+}
+
+/// The cave reduced to the valves worth opening: everything else is only ever walked
+/// through, so all that matters is how long it takes to get from one useful valve to
+/// the next.
+#[derive(Debug)]
+pub struct Network {
+    /// Flow rate of each useful valve; its index is its bit in a set of open valves.
+    flow_rates: Vec<u32>,
+    /// Minutes to walk between useful valves.
+    distances: Vec<Vec<u32>>,
+    /// Minutes to walk from `AA` to each useful valve.
+    from_start: Vec<u32>,
+}
+
+impl Network {
+    fn new(valves: &[Valve]) -> Result<Self, ParseError> {
+        let index: HashMap<&str, usize> = valves
+            .iter()
+            .enumerate()
+            .map(|(i, valve)| (valve.name.as_str(), i))
+            .collect();
+        let start = *index
+            .get(START)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::MissingToken, START))?;
+
+        // Floyd–Warshall over the whole cave; there are only a few dozen valves
+        let unreachable = u32::MAX / 2;
+        let mut all = vec![vec![unreachable; valves.len()]; valves.len()];
+        for (from, valve) in valves.iter().enumerate() {
+            all[from][from] = 0;
+            for tunnel in &valve.tunnels {
+                let to = *index.get(tunnel.as_str()).ok_or_else(|| {
+                    ParseError::new(ParseErrorKind::InvalidLayout, tunnel).on_line(from + 1)
+                })?;
+                all[from][to] = 1;
+            }
+        }
+        for via in 0..valves.len() {
+            for from in 0..valves.len() {
+                for to in 0..valves.len() {
+                    all[from][to] = all[from][to].min(all[from][via] + all[via][to]);
+                }
+            }
+        }
+
+        let useful: Vec<usize> = (0..valves.len())
+            .filter(|&i| valves[i].flow_rate > 0)
+            .collect();
+        if useful.len() > MAX_USEFUL_VALVES {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidLayout,
+                &format!("{} valves with a flow rate", useful.len()),
+            ));
+        }
+
+        Ok(Self {
+            flow_rates: useful.iter().map(|&i| valves[i].flow_rate).collect(),
+            distances: useful
+                .iter()
+                .map(|&from| useful.iter().map(|&to| all[from][to]).collect())
+                .collect(),
+            from_start: useful.iter().map(|&to| all[start][to]).collect(),
+        })
+    }
+
+    /// The most pressure that can be released by opening exactly the valves in each set,
+    /// indexed by the set's bitmask, in `minutes`. Sets that can't all be opened in time
+    /// stay at 0.
+    fn best_per_set(&self, minutes: u32) -> Vec<u32> {
+        let mut best = vec![0; 1 << self.flow_rates.len()];
+        for valve in 0..self.flow_rates.len() {
+            self.visit(valve, self.from_start[valve], minutes, 0, 0, &mut best);
+        }
+        best
+    }
+
+    /// Walks to `valve` (which takes `walk` minutes) and opens it, then tries every
+    /// closed valve from there.
+    fn visit(
+        &self,
+        valve: usize,
+        walk: u32,
+        minutes_left: u32,
+        open: usize,
+        released: u32,
+        best: &mut [u32],
+    ) {
+        // walking there and opening it needs to leave at least a minute of flow
+        if walk + 1 >= minutes_left {
+            return;
+        }
+        let minutes_left = minutes_left - walk - 1;
+        let open = open | 1 << valve;
+        let released = released + self.flow_rates[valve] * minutes_left;
+        best[open] = best[open].max(released);
+
+        for next in 0..self.flow_rates.len() {
+            if open & 1 << next == 0 {
+                let walk = self.distances[valve][next];
+                self.visit(next, walk, minutes_left, open, released, best);
+            }
+        }
+    }
+}
+
+#[aoc_generator(day16)]
+fn input_generator(input: &str) -> Result<Network, ParseError> {
+    let valves: Vec<Valve> = parse_lines(16, input)?;
+    Network::new(&valves).map_err(|e| e.in_day(16))
+}
+
+#[aoc(day16, part1)]
+fn part1(network: &Network) -> u32 {
+    network.best_per_set(30).into_iter().max().unwrap_or(0)
+}
+
+#[aoc(day16, part2)]
+fn part2(network: &Network) -> u32 {
+    let mut best = network.best_per_set(26);
+
+    // let every set also stand for the best of its subsets, so the elephant's share can
+    // be looked up as "anything I didn't open"
+    for valve in 0..network.flow_rates.len() {
+        for set in 0..best.len() {
+            if set & 1 << valve != 0 {
+                best[set] = best[set].max(best[set ^ 1 << valve]);
+            }
+        }
+    }
+
+    let all = best.len() - 1;
+    (0..best.len())
+        .map(|mine| best[mine] + best[all ^ mine])
+        .max()
+        .unwrap_or(0)
+}
+
+solution!(Day16, 16, "Proboscidea Volcanium", Network, u32, u32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(1651, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(1707, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("valves FF, DD", "valves FF, XX");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+        assert_eq!((16, 5, "XX"), (error.day, error.line, error.text.as_str()));
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+/// The five rocks, bottom row first. Each row is a bitmask of the 7-wide chamber with
+/// the leftmost column as the highest bit, already placed two units from the left wall.
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+const LEFT_WALL: u8 = 0b1000000;
+const RIGHT_WALL: u8 = 0b0000001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Jet {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+pub struct Jets(Vec<Jet>);
+
+/// The top of the tower, as how far down each column's highest rock is. Together with
+/// which rock and jet come next it decides everything that happens from here on.
+type Surface = [usize; 7];
+
+#[derive(Debug, Default)]
+struct Chamber {
+    /// Settled rock, bottom row first; the tower is exactly as high as there are rows.
+    rows: Vec<u8>,
+    rocks_dropped: u64,
+    next_jet: usize,
+}
+
+impl Chamber {
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn fits(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter().enumerate().all(|(i, row)| {
+            self.rows
+                .get(bottom + i)
+                .is_none_or(|settled| settled & row == 0)
+        })
+    }
+
+    fn drop_rock(&mut self, jets: &Jets) {
+        let shape = ROCKS[(self.rocks_dropped % ROCKS.len() as u64) as usize];
+        let mut rock = shape.to_vec();
+        let mut bottom = self.height() + 3;
+
+        loop {
+            let jet = jets.0[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % jets.0.len();
+
+            let pushed: Vec<u8> = match jet {
+                Jet::Left if rock.iter().all(|row| row & LEFT_WALL == 0) => {
+                    rock.iter().map(|row| row << 1).collect()
+                }
+                Jet::Right if rock.iter().all(|row| row & RIGHT_WALL == 0) => {
+                    rock.iter().map(|row| row >> 1).collect()
+                }
+                _ => rock.clone(),
+            };
+            if self.fits(&pushed, bottom) {
+                rock = pushed;
+            }
+
+            if bottom == 0 || !self.fits(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+
+        for (i, row) in rock.iter().enumerate() {
+            match self.rows.get_mut(bottom + i) {
+                Some(settled) => *settled |= row,
+                None => self.rows.push(*row),
+            }
+        }
+        self.rocks_dropped += 1;
+    }
+
+    fn surface(&self) -> Surface {
+        let mut surface = [self.height(); 7];
+        for (column, depth) in surface.iter_mut().enumerate() {
+            let bit = LEFT_WALL >> column;
+            if let Some(found) = self.rows.iter().rev().position(|row| row & bit != 0) {
+                *depth = found;
+            }
+        }
+        surface
+    }
+}
+
+/// Height of the tower after `rocks` have come to rest.
+///
+/// Once the same rock, jet and surface come around again the tower just repeats itself,
+/// so whole cycles are skipped and only what's left over is simulated.
+fn tower_height(jets: &Jets, rocks: u64) -> u64 {
+    let mut chamber = Chamber::default();
+    let mut seen: HashMap<(usize, usize, Surface), (u64, usize)> = HashMap::new();
+    let mut skipped_height = None;
+
+    while chamber.rocks_dropped < rocks {
+        chamber.drop_rock(jets);
+        if skipped_height.is_some() {
+            continue;
+        }
+
+        let rock = (chamber.rocks_dropped % ROCKS.len() as u64) as usize;
+        let key = (rock, chamber.next_jet, chamber.surface());
+        let now = (chamber.rocks_dropped, chamber.height());
+
+        if let Some((rocks_before, height_before)) = seen.insert(key, now) {
+            let cycle = now.0 - rocks_before;
+            let growth = (now.1 - height_before) as u64;
+            let cycles = (rocks - chamber.rocks_dropped) / cycle;
+            chamber.rocks_dropped += cycles * cycle;
+            skipped_height = Some(cycles * growth);
+        }
+    }
+
+    chamber.height() as u64 + skipped_height.unwrap_or(0)
+}
+
+#[aoc_generator(day17)]
+fn input_generator(input: &str) -> Result<Jets, ParseError> {
+    let input = input.trim_end();
+    let jets = input
+        .char_indices()
+        .map(|(index, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(ParseError::at_token(
+                ParseErrorKind::UnknownToken,
+                input,
+                &input[index..index + c.len_utf8()],
+            )
+            .in_day(17)
+            .on_line(1)),
+        })
+        .collect::<Result<Vec<Jet>, ParseError>>()?;
+
+    if jets.is_empty() {
+        return Err(ParseError::new(ParseErrorKind::MissingToken, input)
+            .in_day(17)
+            .on_line(1));
+    }
+
+    Ok(Jets(jets))
+}
+
+#[aoc(day17, part1)]
+fn part1(jets: &Jets) -> u64 {
+    tower_height(jets, 2022)
+}
+
+#[aoc(day17, part2)]
+fn part2(jets: &Jets) -> u64 {
+    tower_height(jets, 1_000_000_000_000)
+}
+
+solution!(Day17, 17, "Pyroclastic Flow", Jets, u64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(3068, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(1514285714288, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_cycle_skipping_matches_simulation() {
+        let jets = input_generator(INPUT).unwrap();
+        let mut chamber = Chamber::default();
+        for rocks in 1..=500 {
+            chamber.drop_rock(&jets);
+            assert_eq!(chamber.height() as u64, tower_height(&jets, rocks));
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator(">><x<").unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!((17, 1, 4), (error.day, error.line, error.column));
+    }
+}
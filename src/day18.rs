@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+use crate::voxel::{face_neighbors, Voxel, VoxelSet};
+
+/// One `x,y,z` line of the scan.
+#[derive(Debug, Clone, Copy)]
+struct Cube(Voxel);
+
+impl FromStr for Cube {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coordinates = s
+            .split(',')
+            .map(|token| parse_number(s, token))
+            .collect::<Result<Vec<i32>, ParseError>>()?;
+
+        match coordinates[..] {
+            [x, y, z] => Ok(Self((x, y, z))),
+            _ => Err(ParseError::at_token(ParseErrorKind::InvalidLayout, s, s)),
+        }
+    }
+}
+
+/// Faces of the droplet that steam could reach from outside.
+///
+/// Floods the air in a box one larger than the droplet on every side, counting every
+/// time the water runs into lava; pockets of air trapped inside are never reached.
+fn exterior_surface_area(droplet: &VoxelSet) -> usize {
+    let Some((min, max)) = droplet.bounds() else {
+        return 0;
+    };
+    let (min, max) = (
+        (min.0 - 1, min.1 - 1, min.2 - 1),
+        (max.0 + 1, max.1 + 1, max.2 + 1),
+    );
+    let in_box = |(x, y, z): Voxel| {
+        (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y) && (min.2..=max.2).contains(&z)
+    };
+
+    let mut outside = VoxelSet::new();
+    outside.insert(min);
+    let mut stack = vec![min];
+    let mut faces = 0;
+
+    while let Some(voxel) = stack.pop() {
+        for neighbor in face_neighbors(voxel).filter(|&n| in_box(n)) {
+            if droplet.contains(neighbor) {
+                faces += 1;
+            } else if outside.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    faces
+}
+
+#[aoc_generator(day18)]
+fn input_generator(input: &str) -> Result<VoxelSet, ParseError> {
+    let cubes: Vec<Cube> = parse_lines(18, input)?;
+    Ok(cubes.into_iter().map(|cube| cube.0).collect())
+}
+
+#[aoc(day18, part1)]
+fn part1(droplet: &VoxelSet) -> usize {
+    droplet.surface_area()
+}
+
+#[aoc(day18, part2)]
+fn part2(droplet: &VoxelSet) -> usize {
+    exterior_surface_area(droplet)
+}
+
+solution!(Day18, 18, "Boiling Boulders", VoxelSet, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(64, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(58, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("2,2,6", "2,2");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+        assert_eq!((18, 9, 1), (error.day, error.line, error.column));
+    }
+}
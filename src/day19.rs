@@ -0,0 +1,230 @@
+use std::{str::FromStr, thread};
+
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+/// What every blueprint looks like, with `{}` standing in for its numbers.
+const TEMPLATE: &str = "Blueprint {}: Each ore robot costs {} ore. \
+Each clay robot costs {} ore. \
+Each obsidian robot costs {} ore and {} clay. \
+Each geode robot costs {} ore and {} obsidian.";
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct Blueprint {
+    id: u32,
+    /// `costs[robot]` is the ore, clay and obsidian it takes to build that robot.
+    costs: [[u32; 3]; 4],
+    /// Most of each resource any robot needs; a factory can only build one robot a
+    /// minute, so more robots than this collecting it would be wasted.
+    max_spend: [u32; 3],
+}
+
+impl FromStr for Blueprint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let mut numbers = vec![];
+
+        for expected in TEMPLATE.split_whitespace() {
+            let token = tokens.next().ok_or_else(|| {
+                ParseError::at_token(ParseErrorKind::MissingToken, s, &s[s.len()..])
+            })?;
+
+            match expected.split_once("{}") {
+                Some((prefix, suffix)) => {
+                    let number = token
+                        .strip_prefix(prefix)
+                        .and_then(|rest| rest.strip_suffix(suffix))
+                        .ok_or_else(|| {
+                            ParseError::at_token(ParseErrorKind::UnknownToken, s, token)
+                        })?;
+                    numbers.push(parse_number(s, number)?);
+                }
+                None if token != expected => {
+                    return Err(ParseError::at_token(ParseErrorKind::UnknownToken, s, token));
+                }
+                None => {}
+            }
+        }
+        if let Some(token) = tokens.next() {
+            return Err(ParseError::at_token(ParseErrorKind::UnknownToken, s, token));
+        }
+
+        let costs = [
+            [numbers[1], 0, 0],
+            [numbers[2], 0, 0],
+            [numbers[3], numbers[4], 0],
+            [numbers[5], 0, numbers[6]],
+        ];
+        let max_spend =
+            [ORE, CLAY, OBSIDIAN].map(|resource| costs.iter().map(|c| c[resource]).max().unwrap());
+
+        Ok(Self {
+            id: numbers[0],
+            costs,
+            max_spend,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes_left: u32,
+    /// Ore, clay and obsidian robots.
+    robots: [u32; 3],
+    /// Ore, clay and obsidian in stock.
+    stock: [u32; 3],
+    /// Geodes that will have been cracked by the end, counting robots already built.
+    geodes: u32,
+}
+
+impl Blueprint {
+    /// Most geodes this blueprint can crack in `minutes`.
+    fn max_geodes(&self, minutes: u32) -> u32 {
+        let mut best = 0;
+        let start = State {
+            minutes_left: minutes,
+            robots: [1, 0, 0],
+            stock: [0, 0, 0],
+            geodes: 0,
+        };
+        self.search(start, &mut best);
+        best
+    }
+
+    /// Depth-first over which robot to build next (waiting as long as it takes), rather
+    /// than over what to do each minute.
+    fn search(&self, state: State, best: &mut u32) {
+        *best = (*best).max(state.geodes);
+
+        // even a new geode robot every remaining minute couldn't beat what we've got
+        let m = state.minutes_left;
+        if state.geodes + m * m.saturating_sub(1) / 2 <= *best {
+            return;
+        }
+
+        for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+            if robot != GEODE && state.robots[robot] >= self.max_spend[robot] {
+                continue;
+            }
+            let Some(wait) = self.minutes_until_affordable(&state, robot) else {
+                continue;
+            };
+            // a robot finished in the last minute can't collect anything
+            if wait + 1 >= state.minutes_left {
+                continue;
+            }
+
+            let minutes_left = state.minutes_left - wait - 1;
+            let mut next = State {
+                minutes_left,
+                ..state
+            };
+            for resource in [ORE, CLAY, OBSIDIAN] {
+                let stock = state.stock[resource] + state.robots[resource] * (wait + 1)
+                    - self.costs[robot][resource];
+                // anything beyond what could ever be spent is as good as nothing
+                next.stock[resource] = stock.min(self.max_spend[resource] * minutes_left);
+            }
+            if robot == GEODE {
+                next.geodes += minutes_left;
+            } else {
+                next.robots[robot] += 1;
+            }
+
+            self.search(next, best);
+        }
+    }
+
+    /// Minutes to wait before `robot` can be paid for, or `None` if no robot is collecting
+    /// something it needs.
+    fn minutes_until_affordable(&self, state: &State, robot: usize) -> Option<u32> {
+        let mut wait = 0;
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            let cost = self.costs[robot][resource];
+            let stock = state.stock[resource];
+            if cost > stock {
+                let rate = state.robots[resource];
+                if rate == 0 {
+                    return None;
+                }
+                wait = wait.max((cost - stock).div_ceil(rate));
+            }
+        }
+        Some(wait)
+    }
+}
+
+/// Most geodes for each blueprint in `minutes`, searching every blueprint on its own thread.
+fn max_geodes(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| scope.spawn(move || blueprint.max_geodes(minutes)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("blueprint search panicked"))
+            .collect()
+    })
+}
+
+#[aoc_generator(day19)]
+fn input_generator(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    parse_lines(19, input)
+}
+
+#[aoc(day19, part1)]
+fn part1(blueprints: &[Blueprint]) -> u32 {
+    blueprints
+        .iter()
+        .zip(max_geodes(blueprints, 24))
+        .map(|(blueprint, geodes)| blueprint.id * geodes)
+        .sum()
+}
+
+#[aoc(day19, part2)]
+fn part2(blueprints: &[Blueprint]) -> u32 {
+    let first = &blueprints[..blueprints.len().min(3)];
+    max_geodes(first, 32).into_iter().product()
+}
+
+solution!(Day19, 19, "Not Enough Minerals", Vec<Blueprint>, u32, u32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(33, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(vec![56, 62], max_geodes(&parsed_input, 32));
+        assert_eq!(56 * 62, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("costs 3 ore and 8 clay", "costs 3 ore and 8 sand");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!(
+            (19, 2, 109, "sand."),
+            (error.day, error.line, error.column, error.text.as_str())
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use std::str::FromStr;
 
+use crate::error::{parse_lines, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
 #[derive(Debug, Clone)]
 enum Shape {
     Rock,
@@ -8,14 +11,14 @@ enum Shape {
 }
 
 impl FromStr for Shape {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let shape = match s {
             "A" | "X" => Self::Rock,
             "B" | "Y" => Self::Paper,
             "C" | "Z" => Self::Scissors,
-            _ => panic!("Unrecognized hand shape"),
+            _ => return Err(ParseError::at_token(ParseErrorKind::UnknownToken, s, s)),
         };
 
         Ok(shape)
@@ -33,11 +36,27 @@ impl From<&Shape> for i32 {
 }
 
 #[derive(Debug, Clone)]
-struct Round {
+pub struct Round {
     opponent: Shape,
     mine: Shape,
 }
 
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (opponent, mine) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, s))?;
+
+        Ok(Round {
+            opponent: Shape::from_str(opponent)?,
+            // the second shape starts after the opponent's shape and the space
+            mine: Shape::from_str(mine).map_err(|e| e.shift_column(opponent.len() + 1))?,
+        })
+    }
+}
+
 impl Round {
     fn score(&self) -> i32 {
         match (&self.opponent, &self.mine) {
@@ -100,17 +119,8 @@ impl TargetResult {
 }
 
 #[aoc_generator(day2)]
-fn input_generator(input: &str) -> Vec<Round> {
-    input
-        .lines()
-        .map(|r| {
-            let shapes = r.split_once(' ').unwrap();
-            Round {
-                opponent: Shape::from_str(shapes.0).unwrap(),
-                mine: Shape::from_str(shapes.1).unwrap(),
-            }
-        })
-        .collect()
+fn input_generator(input: &str) -> Result<Vec<Round>, ParseError> {
+    parse_lines(2, input)
 }
 
 // First Column
@@ -145,6 +155,8 @@ fn part2(input: &[Round]) -> i32 {
         .sum()
 }
 
+solution!(Day2, 2, "Rock Paper Scissors", Vec<Round>, i32, i32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,13 +166,20 @@ C Z";
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(15, part1(&parsed_input))
     }
 
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(12, part2(&parsed_input))
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator("A Y\nB Q").unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!((2, 2, 3), (error.day, error.line, error.column));
+    }
 }
@@ -0,0 +1,208 @@
+use crate::error::{parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+const DECRYPTION_KEY: i64 = 811_589_153;
+
+/// The numbers in the encrypted file, in their original order.
+#[derive(Debug)]
+pub struct File(Vec<i64>);
+
+/// The circular list being mixed, holding each number's index in the original file so
+/// duplicate values stay distinct.
+///
+/// It's split into buckets of roughly `sqrt(n)` entries, so finding, removing and
+/// inserting only scans a bucket or the bucket lengths, instead of the whole list.
+#[derive(Debug)]
+struct MixingList {
+    buckets: Vec<Vec<usize>>,
+    /// Which bucket each original index is currently in.
+    bucket_of: Vec<usize>,
+    bucket_size: usize,
+}
+
+impl MixingList {
+    fn new(len: usize) -> Self {
+        let bucket_size = ((len as f64).sqrt() as usize).max(1);
+        let buckets: Vec<Vec<usize>> = (0..len)
+            .collect::<Vec<usize>>()
+            .chunks(bucket_size)
+            .map(<[usize]>::to_vec)
+            .collect();
+        let bucket_of = (0..len).map(|index| index / bucket_size).collect();
+
+        Self {
+            buckets,
+            bucket_of,
+            bucket_size,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bucket_of.len()
+    }
+
+    /// Current position of the number that was at `index` in the file.
+    fn position(&self, index: usize) -> usize {
+        let bucket = self.bucket_of[index];
+        let before: usize = self.buckets[..bucket].iter().map(Vec::len).sum();
+        let within = self.buckets[bucket]
+            .iter()
+            .position(|&i| i == index)
+            .unwrap();
+        before + within
+    }
+
+    /// The bucket `position` falls in and where inside it. A position just past the end
+    /// lands at the end of the last bucket.
+    fn locate(&self, mut position: usize) -> (usize, usize) {
+        for (bucket, entries) in self.buckets.iter().enumerate() {
+            if position < entries.len() {
+                return (bucket, position);
+            }
+            position -= entries.len();
+        }
+        let last = self.buckets.len() - 1;
+        (last, self.buckets[last].len() + position)
+    }
+
+    /// Original index of the number at `position`.
+    fn get(&self, position: usize) -> usize {
+        let (bucket, within) = self.locate(position % self.len());
+        self.buckets[bucket][within]
+    }
+
+    fn remove(&mut self, index: usize) -> usize {
+        let position = self.position(index);
+        let bucket = self.bucket_of[index];
+        self.buckets[bucket].retain(|&i| i != index);
+        position
+    }
+
+    fn insert(&mut self, position: usize, index: usize) {
+        let (bucket, within) = self.locate(position);
+        self.buckets[bucket].insert(within, index);
+        self.bucket_of[index] = bucket;
+
+        // keep buckets small so scanning one stays cheap
+        if self.buckets[bucket].len() > 2 * self.bucket_size {
+            let tail = self.buckets[bucket].split_off(self.bucket_size);
+            self.buckets.insert(bucket + 1, tail);
+            // splits only happen every `bucket_size` inserts or so, which pays for this
+            for (offset, entries) in self.buckets[bucket + 1..].iter().enumerate() {
+                for &i in entries {
+                    self.bucket_of[i] = bucket + 1 + offset;
+                }
+            }
+        }
+    }
+
+    /// Moves every number, in file order, forward or back by its own value.
+    fn mix(&mut self, numbers: &[i64]) {
+        // with the number itself taken out there are `len - 1` gaps to land in
+        let gaps = self.len() as i64 - 1;
+        if gaps == 0 {
+            return;
+        }
+
+        for (index, &number) in numbers.iter().enumerate() {
+            let position = self.remove(index) as i64;
+            let target = (position + number).rem_euclid(gaps) as usize;
+            self.insert(target, index);
+        }
+    }
+}
+
+/// Sum of the numbers 1000, 2000 and 3000 after the 0, once mixed `rounds` times.
+fn grove_coordinates(numbers: &[i64], rounds: usize) -> i64 {
+    let mut list = MixingList::new(numbers.len());
+    for _ in 0..rounds {
+        list.mix(numbers);
+    }
+
+    let zero = numbers.iter().position(|&n| n == 0).unwrap();
+    let start = list.position(zero);
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| numbers[list.get(start + offset)])
+        .sum()
+}
+
+#[aoc_generator(day20)]
+fn input_generator(input: &str) -> Result<File, ParseError> {
+    let numbers = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            parse_number(line, line.trim()).map_err(|e| e.in_day(20).on_line(index + 1))
+        })
+        .collect::<Result<Vec<i64>, ParseError>>()?;
+
+    if numbers.iter().filter(|&&n| n == 0).count() != 1 {
+        return Err(ParseError::new(ParseErrorKind::InvalidLayout, "0").in_day(20));
+    }
+
+    Ok(File(numbers))
+}
+
+#[aoc(day20, part1)]
+fn part1(file: &File) -> i64 {
+    grove_coordinates(&file.0, 1)
+}
+
+#[aoc(day20, part2)]
+fn part2(file: &File) -> i64 {
+    let numbers: Vec<i64> = file.0.iter().map(|n| n * DECRYPTION_KEY).collect();
+    grove_coordinates(&numbers, 10)
+}
+
+solution!(Day20, 20, "Grove Positioning System", File, i64, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "1
+2
+-3
+3
+-2
+0
+4";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(3, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(1623178306, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_mix_matches_vec() {
+        // plenty of duplicates, and values larger than the list
+        let numbers: Vec<i64> = (0..200).map(|i: i64| (i * 7919 % 401) - 200).collect();
+
+        let mut expected: Vec<usize> = (0..numbers.len()).collect();
+        for (index, &number) in numbers.iter().enumerate() {
+            let position = expected.iter().position(|&i| i == index).unwrap();
+            expected.remove(position);
+            let target = (position as i64 + number).rem_euclid(expected.len() as i64);
+            expected.insert(target as usize, index);
+        }
+
+        let mut list = MixingList::new(numbers.len());
+        list.mix(&numbers);
+        let actual: Vec<usize> = (0..numbers.len()).map(|p| list.get(p)).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator("1\n2\nx\n0").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumber, error.kind);
+        assert_eq!((20, 3, 1), (error.day, error.line, error.column));
+    }
+}
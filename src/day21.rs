@@ -0,0 +1,331 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::error::{parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl FromStr for Operator {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Self::Add),
+            "-" => Ok(Self::Subtract),
+            "*" => Ok(Self::Multiply),
+            "/" => Ok(Self::Divide),
+            _ => Err(ParseError::at_token(ParseErrorKind::UnknownToken, s, s)),
+        }
+    }
+}
+
+impl Operator {
+    fn apply(&self, lhs: i64, rhs: i64) -> i64 {
+        match self {
+            Operator::Add => lhs + rhs,
+            Operator::Subtract => lhs - rhs,
+            Operator::Multiply => lhs * rhs,
+            Operator::Divide => exact_division(lhs, rhs),
+        }
+    }
+}
+
+/// Monkeys only ever shout whole numbers, so a division that leaves a remainder means
+/// the input (or a guess for `humn`) is wrong.
+fn exact_division(lhs: i64, rhs: i64) -> i64 {
+    assert!(
+        rhs != 0 && lhs % rhs == 0,
+        "{} / {} is not a whole number",
+        lhs,
+        rhs
+    );
+    lhs / rhs
+}
+
+/// A monkey's job, with the monkeys it listens to as indices into [`Troop`].
+#[derive(Debug, Clone, Copy)]
+enum Job {
+    Number(i64),
+    Operation(usize, Operator, usize),
+}
+
+/// Every monkey's job, wired up as a DAG.
+#[derive(Debug)]
+pub struct Troop {
+    jobs: Vec<Job>,
+    /// Every monkey after the monkeys it listens to.
+    order: Vec<usize>,
+    root: usize,
+    human: usize,
+}
+
+impl Troop {
+    /// What every monkey yells, indexed like `jobs`.
+    fn evaluate(&self) -> Vec<i64> {
+        let mut values = vec![0; self.jobs.len()];
+        for &monkey in &self.order {
+            values[monkey] = match self.jobs[monkey] {
+                Job::Number(value) => value,
+                Job::Operation(lhs, operator, rhs) => operator.apply(values[lhs], values[rhs]),
+            };
+        }
+        values
+    }
+
+    /// Whether each monkey's number depends on what `humn` yells.
+    fn depends_on_human(&self) -> Vec<bool> {
+        let mut depends = vec![false; self.jobs.len()];
+        depends[self.human] = true;
+        for &monkey in &self.order {
+            if let Job::Operation(lhs, _, rhs) = self.jobs[monkey] {
+                depends[monkey] = depends[lhs] || depends[rhs];
+            }
+        }
+        depends
+    }
+
+    /// What `humn` has to yell for both sides of `root` to match.
+    ///
+    /// Works down from `root`, undoing one operation at a time, which only works while
+    /// exactly one side of each operation depends on `humn` and the other is a plain
+    /// number. Monkeys can share listeners, so both sides may lead back to `humn`
+    /// (`humn + humn`, say); that isn't solved and gives `None`.
+    fn solve_for_human(&self) -> Option<i64> {
+        let values = self.evaluate();
+        let depends = self.depends_on_human();
+
+        let Job::Operation(lhs, _, rhs) = self.jobs[self.root] else {
+            return None;
+        };
+        let (mut monkey, mut target) = match (depends[lhs], depends[rhs]) {
+            (true, false) => (lhs, values[rhs]),
+            (false, true) => (rhs, values[lhs]),
+            _ => return None,
+        };
+
+        while monkey != self.human {
+            let Job::Operation(lhs, operator, rhs) = self.jobs[monkey] else {
+                return None;
+            };
+            (monkey, target) = match (depends[lhs], depends[rhs]) {
+                (true, false) => {
+                    let known = values[rhs];
+                    let target = match operator {
+                        // target = x + known, and so on
+                        Operator::Add => target - known,
+                        Operator::Subtract => target + known,
+                        Operator::Multiply => exact_division(target, known),
+                        Operator::Divide => target * known,
+                    };
+                    (lhs, target)
+                }
+                (false, true) => {
+                    let known = values[lhs];
+                    let target = match operator {
+                        // target = known + x, and so on
+                        Operator::Add => target - known,
+                        Operator::Subtract => known - target,
+                        Operator::Multiply => exact_division(target, known),
+                        Operator::Divide => exact_division(known, target),
+                    };
+                    (rhs, target)
+                }
+                _ => return None,
+            };
+        }
+
+        Some(target)
+    }
+}
+
+/// Orders monkeys so everyone comes after the monkeys they listen to, or returns one
+/// that ends up (indirectly) listening to itself.
+fn topological_order(jobs: &[Job]) -> Result<Vec<usize>, usize> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Mark {
+        New,
+        Visiting,
+        Done,
+    }
+
+    let mut marks = vec![Mark::New; jobs.len()];
+    let mut order = Vec::with_capacity(jobs.len());
+
+    for start in 0..jobs.len() {
+        // (monkey, whether its inputs have been pushed already)
+        let mut stack = vec![(start, false)];
+        while let Some((monkey, expanded)) = stack.pop() {
+            if expanded {
+                marks[monkey] = Mark::Done;
+                order.push(monkey);
+                continue;
+            }
+            match marks[monkey] {
+                Mark::Done => continue,
+                Mark::Visiting => return Err(monkey),
+                Mark::New => {}
+            }
+
+            marks[monkey] = Mark::Visiting;
+            stack.push((monkey, true));
+            if let Job::Operation(lhs, _, rhs) = jobs[monkey] {
+                for input in [lhs, rhs] {
+                    match marks[input] {
+                        Mark::Done => {}
+                        Mark::Visiting => return Err(input),
+                        Mark::New => stack.push((input, false)),
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(order)
+}
+
+/// Parses the `job` part of `line`, looking up the monkeys it listens to in `index`.
+fn parse_job(line: &str, job: &str, index: &HashMap<&str, usize>) -> Result<Job, ParseError> {
+    let monkey = |name: &str| {
+        index
+            .get(name)
+            .copied()
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::InvalidLayout, line, name))
+    };
+
+    match job.split(' ').collect::<Vec<&str>>()[..] {
+        [number] => Ok(Job::Number(parse_number(line, number)?)),
+        [lhs, operator, rhs] => {
+            let operator = Operator::from_str(operator)
+                .map_err(|_| ParseError::at_token(ParseErrorKind::UnknownToken, line, operator))?;
+            Ok(Job::Operation(monkey(lhs)?, operator, monkey(rhs)?))
+        }
+        _ => Err(ParseError::at_token(
+            ParseErrorKind::UnknownToken,
+            line,
+            job,
+        )),
+    }
+}
+
+#[aoc_generator(day21)]
+fn input_generator(input: &str) -> Result<Troop, ParseError> {
+    let in_line = |index: usize| move |e: ParseError| e.in_day(21).on_line(index + 1);
+
+    // (line, name, job)
+    let monkeys: Vec<(&str, &str, &str)> = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            line.split_once(": ")
+                .map(|(name, job)| (line, name, job))
+                .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, line, line))
+                .map_err(in_line(index))
+        })
+        .collect::<Result<_, _>>()?;
+    let index: HashMap<&str, usize> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, (_, name, _))| (*name, i))
+        .collect();
+
+    let jobs = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, &(line, _, job))| parse_job(line, job, &index).map_err(in_line(i)))
+        .collect::<Result<Vec<Job>, ParseError>>()?;
+
+    let find = |name: &str| {
+        index
+            .get(name)
+            .copied()
+            .ok_or_else(|| ParseError::new(ParseErrorKind::MissingToken, name).in_day(21))
+    };
+    let root = find(ROOT)?;
+    let human = find(HUMAN)?;
+    let order = topological_order(&jobs).map_err(|monkey| {
+        ParseError::new(ParseErrorKind::InvalidLayout, monkeys[monkey].1)
+            .in_day(21)
+            .on_line(monkey + 1)
+    })?;
+
+    Ok(Troop {
+        jobs,
+        order,
+        root,
+        human,
+    })
+}
+
+#[aoc(day21, part1)]
+fn part1(troop: &Troop) -> i64 {
+    troop.evaluate()[troop.root]
+}
+
+#[aoc(day21, part2)]
+fn part2(troop: &Troop) -> i64 {
+    troop
+        .solve_for_human()
+        .expect("humn should appear on exactly one side of every operation above it")
+}
+
+solution!(Day21, 21, "Monkey Math", Troop, i64, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(152, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(301, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = INPUT.replace("ljgn * ptdq", "ljgn * nope");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+        assert_eq!((21, 13, 14), (error.day, error.line, error.column));
+
+        let input = INPUT.replace("hmdt: 32", "hmdt: drzm + zczc");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+    }
+
+    #[test]
+    fn test_human_on_both_sides() {
+        let troop =
+            input_generator("root: aaaa + bbbb\naaaa: humn + humn\nbbbb: 10\nhumn: 1").unwrap();
+        assert_eq!(None, troop.solve_for_human());
+    }
+}
@@ -0,0 +1,437 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::error::{parse_number, ParseError, ParseErrorKind};
+use crate::grid::{Direction, Grid, Position};
+use crate::solution::solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    /// Off the map.
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Forward(u32),
+    Left,
+    Right,
+}
+
+/// Facing, numbered the way the password wants it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facing {
+    Right = 0,
+    Down = 1,
+    Left = 2,
+    Up = 3,
+}
+
+impl Facing {
+    const ALL: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+    fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    fn direction(self) -> Direction {
+        match self {
+            Facing::Right => Direction::Right,
+            Facing::Down => Direction::Down,
+            Facing::Left => Direction::Left,
+            Facing::Up => Direction::Up,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Notes {
+    board: Grid<Tile>,
+    path: Vec<Instruction>,
+}
+
+impl Notes {
+    /// Follows the path from the leftmost open tile of the top row, letting `wrap` decide
+    /// where a step off the edge of the map lands, and returns the final password.
+    fn follow(&self, wrap: impl Fn(Position, Facing) -> (Position, Facing)) -> usize {
+        let column = self.board.row(0).iter().position(|&t| t == Tile::Open);
+        let mut position = (0, column.expect("no open tile on the top row"));
+        let mut facing = Facing::Right;
+
+        for instruction in &self.path {
+            match instruction {
+                Instruction::Left => facing = facing.turn_left(),
+                Instruction::Right => facing = facing.turn_right(),
+                Instruction::Forward(steps) => {
+                    for _ in 0..*steps {
+                        let next = match self.board.step(position, facing.direction()) {
+                            Some(next) if self.board[next] != Tile::Void => (next, facing),
+                            _ => wrap(position, facing),
+                        };
+                        if self.board[next.0] == Tile::Wall {
+                            break;
+                        }
+                        (position, facing) = next;
+                    }
+                }
+            }
+        }
+
+        1000 * (position.0 + 1) + 4 * (position.1 + 1) + facing as usize
+    }
+
+    /// Part 1: walking off an edge comes back in on the opposite side of the same row
+    /// or column.
+    fn wrap_flat(&self, (row, col): Position, facing: Facing) -> (Position, Facing) {
+        let back = facing.turn_right().turn_right().direction();
+        let mut position = (row, col);
+        while let Some(previous) = self.board.step(position, back) {
+            if self.board[previous] == Tile::Void {
+                break;
+            }
+            position = previous;
+        }
+        (position, facing)
+    }
+}
+
+type Vector = [i32; 3];
+
+fn negate(v: Vector) -> Vector {
+    v.map(|c| -c)
+}
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(v: Vector, by: i32) -> Vector {
+    v.map(|c| c * by)
+}
+
+/// Where a face of the net ended up once folded: which way its columns and rows run,
+/// and which way it faces, as unit vectors around a cube centred on the origin.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    right: Vector,
+    down: Vector,
+    normal: Vector,
+}
+
+impl Frame {
+    fn towards(&self, facing: Facing) -> Vector {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => negate(self.right),
+            Facing::Up => negate(self.down),
+        }
+    }
+
+    /// The frame of the neighbouring face of the net in `facing` once it's folded down
+    /// along the shared edge.
+    fn fold(&self, facing: Facing) -> Self {
+        let Frame {
+            right,
+            down,
+            normal,
+        } = *self;
+        match facing {
+            Facing::Right => Frame {
+                right: negate(normal),
+                down,
+                normal: right,
+            },
+            Facing::Left => Frame {
+                right: normal,
+                down,
+                normal: negate(right),
+            },
+            Facing::Down => Frame {
+                right,
+                down: negate(normal),
+                normal: down,
+            },
+            Facing::Up => Frame {
+                right,
+                down: normal,
+                normal: negate(down),
+            },
+        }
+    }
+}
+
+/// The map folded up into a cube, worked out from the shape of the net alone.
+#[derive(Debug)]
+struct Cube {
+    size: usize,
+    /// Folded frame of each face, keyed by its `(row, col)` block in the net.
+    faces: HashMap<Position, Frame>,
+}
+
+impl Cube {
+    /// Folds the net starting from the first face, or returns `None` if the map isn't
+    /// the net of a cube.
+    fn fold(board: &Grid<Tile>) -> Option<Self> {
+        let area = board.iter().filter(|(_, &t)| t != Tile::Void).count();
+        let size = (1..=area).find(|size| 6 * size * size >= area)?;
+        if 6 * size * size != area
+            || !board.rows().is_multiple_of(size)
+            || !board.cols().is_multiple_of(size)
+        {
+            return None;
+        }
+
+        let blocks = Grid::from_cells(
+            board.rows() / size,
+            board.cols() / size,
+            (0..board.rows() / size)
+                .flat_map(|row| (0..board.cols() / size).map(move |col| (row * size, col * size)))
+                .map(|corner| board[corner] != Tile::Void)
+                .collect(),
+        )?;
+        let first = blocks.find(|&on_map| on_map)?;
+
+        let mut faces = HashMap::from([(
+            first,
+            Frame {
+                right: [1, 0, 0],
+                down: [0, 1, 0],
+                normal: [0, 0, 1],
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(block) = queue.pop_front() {
+            for facing in Facing::ALL {
+                let Some(next) = blocks.step(block, facing.direction()) else {
+                    continue;
+                };
+                if blocks[next] && !faces.contains_key(&next) {
+                    faces.insert(next, faces[&block].fold(facing));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // every face of the net has to land on a different side of the cube
+        let mut normals: Vec<Vector> = faces.values().map(|frame| frame.normal).collect();
+        normals.sort_unstable();
+        normals.dedup();
+        (faces.len() == 6 && normals.len() == 6).then_some(Self { size, faces })
+    }
+
+    fn face_at(&self, (row, col): Position) -> Position {
+        (row / self.size, col / self.size)
+    }
+
+    /// Part 2: walking off a face continues onto whichever face is folded against that
+    /// edge.
+    ///
+    /// Works in doubled coordinates so each tile's centre sits on whole numbers: a tile
+    /// is `normal * size` out from the centre of the cube plus its offset across the
+    /// face. Stepping over an edge moves one unit in the direction of travel and one
+    /// unit back towards the centre, and the new facing is heading away from the face
+    /// that was just left.
+    fn wrap(&self, (row, col): Position, facing: Facing) -> (Position, Facing) {
+        let size = self.size as i32;
+        let block = self.face_at((row, col));
+        let from = self.faces[&block];
+        let (local_row, local_col) = ((row % self.size) as i32, (col % self.size) as i32);
+
+        let point = add(
+            scale(from.normal, size),
+            add(
+                scale(from.right, 2 * local_col - (size - 1)),
+                scale(from.down, 2 * local_row - (size - 1)),
+            ),
+        );
+        let heading = from.towards(facing);
+        let point = add(point, add(heading, negate(from.normal)));
+
+        let (&next_block, to) = self
+            .faces
+            .iter()
+            .find(|(_, frame)| frame.normal == heading)
+            .expect("folded cube is missing a face");
+        let new_facing = Facing::ALL
+            .into_iter()
+            .find(|&f| to.towards(f) == negate(from.normal))
+            .unwrap();
+
+        let local_row = ((dot(point, to.down) + size - 1) / 2) as usize;
+        let local_col = ((dot(point, to.right) + size - 1) / 2) as usize;
+        (
+            (
+                next_block.0 * self.size + local_row,
+                next_block.1 * self.size + local_col,
+            ),
+            new_facing,
+        )
+    }
+}
+
+fn parse_path(line: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut path = vec![];
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits > 0 {
+            path.push(Instruction::Forward(parse_number(line, &rest[..digits])?));
+            rest = &rest[digits..];
+            continue;
+        }
+
+        let turn = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+        path.push(match turn {
+            "L" => Instruction::Left,
+            "R" => Instruction::Right,
+            _ => {
+                return Err(ParseError::at_token(
+                    ParseErrorKind::UnknownToken,
+                    line,
+                    turn,
+                ))
+            }
+        });
+        rest = &rest[turn.len()..];
+    }
+
+    Ok(path)
+}
+
+#[aoc_generator(day22)]
+fn input_generator(input: &str) -> Result<Notes, ParseError> {
+    let (map, path) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidLayout, input).in_day(22))?;
+
+    // lines stop at the last tile, so pad them out to a rectangle
+    let width = map.lines().map(str::len).max().unwrap_or(0);
+    let padded: Vec<String> = map.lines().map(|line| format!("{:width$}", line)).collect();
+    let board = Grid::parse(&padded.join("\n"), |c| match c {
+        ' ' => Ok(Tile::Void),
+        '.' => Ok(Tile::Open),
+        '#' => Ok(Tile::Wall),
+        _ => Err(ParseErrorKind::UnknownToken),
+    })
+    .map_err(|e| e.in_day(22))?;
+
+    let path_line = map.lines().count() + 2;
+    let path = parse_path(path.trim_end()).map_err(|e| e.in_day(22).on_line(path_line))?;
+
+    Ok(Notes { board, path })
+}
+
+#[aoc(day22, part1)]
+fn part1(notes: &Notes) -> usize {
+    notes.follow(|position, facing| notes.wrap_flat(position, facing))
+}
+
+#[aoc(day22, part2)]
+fn part2(notes: &Notes) -> usize {
+    let cube = Cube::fold(&notes.board).expect("the map doesn't fold into a cube");
+    notes.follow(|position, facing| cube.wrap(position, facing))
+}
+
+solution!(Day22, 22, "Monkey Map", Notes, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(6032, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(5031, part2(&parsed_input))
+    }
+
+    /// Blows each `x` of a net up into a `size` by `size` face of open tiles.
+    fn expand(net: &str, size: usize) -> String {
+        net.lines()
+            .flat_map(|line| {
+                let row: String = line
+                    .chars()
+                    .flat_map(|c| std::iter::repeat_n(if c == 'x' { '.' } else { ' ' }, size))
+                    .collect();
+                std::iter::repeat_n(row, size)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Walking off any edge and straight back again has to return to the same tile.
+    fn assert_wraps_back(map: &str) {
+        let notes = input_generator(&format!("{}\n\n1", map)).unwrap();
+        let cube = Cube::fold(&notes.board).unwrap();
+        let reverse = |facing: Facing| facing.turn_right().turn_right();
+
+        for (position, &tile) in notes.board.iter() {
+            if tile == Tile::Void {
+                continue;
+            }
+            for facing in Facing::ALL {
+                let off_edge = notes
+                    .board
+                    .step(position, facing.direction())
+                    .is_none_or(|next| notes.board[next] == Tile::Void);
+                if !off_edge {
+                    continue;
+                }
+
+                let (landed, landed_facing) = cube.wrap(position, facing);
+                assert_ne!(Tile::Void, notes.board[landed]);
+                assert_eq!(
+                    (position, reverse(facing)),
+                    cube.wrap(landed, reverse(landed_facing))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cube_wrap_round_trip() {
+        assert_wraps_back(INPUT.split_once("\n\n").unwrap().0);
+        // the shape of the real inputs, a cross and a staircase
+        for net in [" xx\n x\nxx\nx", " x\nxxx\n x\n x", "x\nxx\n xx\n  x"] {
+            assert_wraps_back(&expand(net, 3));
+        }
+    }
+
+    #[test]
+    fn test_not_a_cube() {
+        let notes = input_generator(&format!("{}\n\n1", expand("xxx\nxxx", 2))).unwrap();
+        assert!(Cube::fold(&notes.board).is_none());
+    }
+}
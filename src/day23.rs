@@ -0,0 +1,198 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::Grid;
+use crate::solution::solution;
+
+/// `(row, col)`; unlike [`crate::grid::Position`] the elves can spread in every direction.
+type Point = (i32, i32);
+
+const NEIGHBORS: [Point; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// The directions elves consider, in the order of the first round: the step itself,
+/// followed by the three positions that have to be empty to take it.
+const PROPOSALS: [(Point, [Point; 3]); 4] = [
+    ((-1, 0), [(-1, -1), (-1, 0), (-1, 1)]),
+    ((1, 0), [(1, -1), (1, 0), (1, 1)]),
+    ((0, -1), [(-1, -1), (0, -1), (1, -1)]),
+    ((0, 1), [(-1, 1), (0, 1), (1, 1)]),
+];
+
+fn offset((row, col): Point, (d_row, d_col): Point) -> Point {
+    (row + d_row, col + d_col)
+}
+
+/// Where the elves start out.
+#[derive(Debug)]
+pub struct Scan(HashSet<Point>);
+
+#[derive(Debug, Clone)]
+pub struct Grove {
+    elves: HashSet<Point>,
+    rounds: usize,
+}
+
+impl Grove {
+    pub fn new(scan: &Scan) -> Self {
+        Self {
+            elves: scan.0.clone(),
+            rounds: 0,
+        }
+    }
+
+    fn proposal(&self, elf: Point) -> Option<Point> {
+        let occupied = |d: &Point| self.elves.contains(&offset(elf, *d));
+        if !NEIGHBORS.iter().any(occupied) {
+            return None;
+        }
+
+        (0..PROPOSALS.len())
+            .map(|i| PROPOSALS[(self.rounds + i) % PROPOSALS.len()])
+            .find(|(_, checks)| !checks.iter().any(occupied))
+            .map(|(step, _)| offset(elf, step))
+    }
+
+    /// Plays one round and returns whether any elf moved.
+    pub fn play_round(&mut self) -> bool {
+        let proposals: Vec<(Point, Point)> = self
+            .elves
+            .iter()
+            .filter_map(|&elf| self.proposal(elf).map(|target| (elf, target)))
+            .collect();
+
+        let mut counts: HashMap<Point, usize> = HashMap::new();
+        for &(_, target) in &proposals {
+            *counts.entry(target).or_default() += 1;
+        }
+
+        let mut moved = false;
+        for (elf, target) in proposals {
+            if counts[&target] == 1 {
+                self.elves.remove(&elf);
+                self.elves.insert(target);
+                moved = true;
+            }
+        }
+
+        self.rounds += 1;
+        moved
+    }
+
+    /// Smallest and largest `(row, col)` with an elf on it.
+    fn bounds(&self) -> (Point, Point) {
+        let rows = self.elves.iter().map(|elf| elf.0);
+        let cols = self.elves.iter().map(|elf| elf.1);
+        (
+            (
+                rows.clone().min().unwrap_or(0),
+                cols.clone().min().unwrap_or(0),
+            ),
+            (rows.max().unwrap_or(0), cols.max().unwrap_or(0)),
+        )
+    }
+
+    pub fn empty_tiles(&self) -> usize {
+        let ((top, left), (bottom, right)) = self.bounds();
+        ((bottom - top + 1) * (right - left + 1)) as usize - self.elves.len()
+    }
+
+    /// Draws the smallest rectangle containing every elf, like the puzzle does.
+    pub fn render(&self) -> String {
+        let ((top, left), (bottom, right)) = self.bounds();
+        (top..=bottom)
+            .map(|row| {
+                (left..=right)
+                    .map(|col| {
+                        if self.elves.contains(&(row, col)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[aoc_generator(day23)]
+fn input_generator(input: &str) -> Result<Scan, ParseError> {
+    let grid = Grid::parse(input, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(ParseErrorKind::UnknownToken),
+    })
+    .map_err(|e| e.in_day(23))?;
+
+    Ok(Scan(
+        grid.iter()
+            .filter(|(_, &elf)| elf)
+            .map(|((row, col), _)| (row as i32, col as i32))
+            .collect(),
+    ))
+}
+
+#[aoc(day23, part1)]
+fn part1(scan: &Scan) -> usize {
+    let mut grove = Grove::new(scan);
+    for _ in 0..10 {
+        grove.play_round();
+    }
+    grove.empty_tiles()
+}
+
+#[aoc(day23, part2)]
+fn part2(scan: &Scan) -> usize {
+    let mut grove = Grove::new(scan);
+    while grove.play_round() {}
+    grove.rounds
+}
+
+solution!(Day23, 23, "Unstable Diffusion", Scan, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(110, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(20, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_render() {
+        let scan = input_generator(".....\n..##.\n..#..\n.....\n..##.\n.....").unwrap();
+        let mut grove = Grove::new(&scan);
+        assert_eq!("##\n#.\n..\n##", grove.render());
+
+        for _ in 0..3 {
+            assert!(grove.play_round());
+        }
+        assert_eq!("..#..\n....#\n#....\n....#\n.....\n..#..", grove.render());
+        assert!(!grove.play_round());
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::{Direction, Grid, Position};
+use crate::solution::solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Wall,
+    Ground,
+    Blizzard(Direction),
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+#[derive(Debug)]
+pub struct Valley {
+    /// Size of the whole map, walls included.
+    rows: usize,
+    cols: usize,
+    start: Position,
+    end: Position,
+    /// Which positions inside the walls have a blizzard on them at each minute of the
+    /// cycle. Blizzards wrap around, so after `lcm(width, height)` minutes they're all
+    /// back where they started.
+    occupied: Vec<Grid<bool>>,
+}
+
+impl Valley {
+    fn new(map: &Grid<Cell>, start: Position, end: Position) -> Self {
+        let (height, width) = (map.rows() - 2, map.cols() - 2);
+        let blizzards: Vec<(Position, Direction)> = map
+            .iter()
+            .filter_map(|((row, col), cell)| match cell {
+                Cell::Blizzard(direction) => Some(((row - 1, col - 1), *direction)),
+                _ => None,
+            })
+            .collect();
+
+        let occupied = (0..lcm(width, height))
+            .map(|minute| {
+                let mut grid = Grid::new(height, width, false);
+                for &((row, col), direction) in &blizzards {
+                    let (d_row, d_col) = direction.offset();
+                    let row = (row as isize + d_row * minute as isize).rem_euclid(height as isize);
+                    let col = (col as isize + d_col * minute as isize).rem_euclid(width as isize);
+                    grid[(row as usize, col as usize)] = true;
+                }
+                grid
+            })
+            .collect();
+
+        Self {
+            rows: map.rows(),
+            cols: map.cols(),
+            start,
+            end,
+            occupied,
+        }
+    }
+
+    fn period(&self) -> usize {
+        self.occupied.len()
+    }
+
+    /// Whether the expedition can be at `(row, col)` at `minute`.
+    fn is_free(&self, (row, col): Position, minute: usize) -> bool {
+        if (row, col) == self.start || (row, col) == self.end {
+            return true;
+        }
+        let inside = (1..self.rows - 1).contains(&row) && (1..self.cols - 1).contains(&col);
+        inside && !self.occupied[minute % self.period()][(row - 1, col - 1)]
+    }
+
+    /// Earliest minute the expedition can reach `to`, setting off from `from` at `minute`.
+    ///
+    /// Breadth-first over (position, minute); since the blizzards repeat, minutes are
+    /// only told apart up to the period when deciding what's been seen.
+    fn crossing(&self, from: Position, to: Position, minute: usize) -> Option<usize> {
+        let mut seen = vec![Grid::new(self.rows, self.cols, false); self.period()];
+        let mut queue = VecDeque::from([(from, minute)]);
+        seen[minute % self.period()][from] = true;
+
+        while let Some((position, minute)) = queue.pop_front() {
+            if position == to {
+                return Some(minute);
+            }
+
+            let (row, col) = position;
+            let moves = [
+                Some(position),
+                row.checked_sub(1).map(|row| (row, col)),
+                Some((row + 1, col)),
+                col.checked_sub(1).map(|col| (row, col)),
+                Some((row, col + 1)),
+            ];
+            for next in moves.into_iter().flatten() {
+                let next_minute = minute + 1;
+                if next.0 >= self.rows || !self.is_free(next, next_minute) {
+                    continue;
+                }
+                let seen = &mut seen[next_minute % self.period()][next];
+                if !*seen {
+                    *seen = true;
+                    queue.push_back((next, next_minute));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[aoc_generator(day24)]
+fn input_generator(input: &str) -> Result<Valley, ParseError> {
+    let map = Grid::parse(input, |c| match c {
+        '#' => Ok(Cell::Wall),
+        '.' => Ok(Cell::Ground),
+        '^' => Ok(Cell::Blizzard(Direction::Up)),
+        'v' => Ok(Cell::Blizzard(Direction::Down)),
+        '<' => Ok(Cell::Blizzard(Direction::Left)),
+        '>' => Ok(Cell::Blizzard(Direction::Right)),
+        _ => Err(ParseErrorKind::UnknownToken),
+    })
+    .map_err(|e| e.in_day(24))?;
+
+    if map.rows() < 3 || map.cols() < 3 {
+        return Err(ParseError::new(ParseErrorKind::InvalidLayout, input).in_day(24));
+    }
+
+    // the only gaps in the top and bottom walls
+    let opening = |row: usize| {
+        let cols: Vec<usize> = (0..map.cols())
+            .filter(|&col| map[(row, col)] == Cell::Ground)
+            .collect();
+        match cols[..] {
+            [col] => Ok((row, col)),
+            _ => Err(ParseError::new(
+                ParseErrorKind::InvalidLayout,
+                input.lines().nth(row).unwrap_or_default(),
+            )
+            .in_day(24)
+            .on_line(row + 1)),
+        }
+    };
+    let start = opening(0)?;
+    let end = opening(map.rows() - 1)?;
+
+    Ok(Valley::new(&map, start, end))
+}
+
+#[aoc(day24, part1)]
+fn part1(valley: &Valley) -> usize {
+    valley
+        .crossing(valley.start, valley.end, 0)
+        .expect("no way through the blizzards")
+}
+
+#[aoc(day24, part2)]
+fn part2(valley: &Valley) -> usize {
+    let (start, end) = (valley.start, valley.end);
+    [(start, end), (end, start), (start, end)]
+        .into_iter()
+        .try_fold(0, |minute, (from, to)| valley.crossing(from, to, minute))
+        .expect("no way through the blizzards")
+}
+
+solution!(Day24, 24, "Blizzard Basin", Valley, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(18, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(54, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_blizzards_cycle() {
+        let valley = input_generator(INPUT).unwrap();
+        assert_eq!(12, valley.period());
+        // the `>` in the top left corner of the valley, a minute later
+        assert!(valley.occupied[1][(0, 1)]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator(&INPUT.replace("######.#", "########")).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+        assert_eq!((24, 6), (error.day, error.line));
+    }
+}
@@ -0,0 +1,238 @@
+use std::{fmt, iter::Sum, num::TryFromIntError, ops::Add, str::FromStr};
+
+use crate::error::{parse_lines, ParseError, ParseErrorKind};
+use crate::solution::Solution;
+
+/// A number in Special Numeral-Analogue Fuel Units: balanced base five, with digits
+/// `=` (-2), `-` (-1), `0`, `1` and `2`.
+///
+/// Stored as an `i128` so anything an `i64` can hold converts losslessly, sums included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snafu(i128);
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new(ParseErrorKind::MissingToken, s));
+        }
+
+        let mut value: i128 = 0;
+        for (index, c) in s.char_indices() {
+            let digit = match c {
+                '2' => 2,
+                '1' => 1,
+                '0' => 0,
+                '-' => -1,
+                '=' => -2,
+                _ => {
+                    return Err(ParseError::at_token(
+                        ParseErrorKind::UnknownToken,
+                        s,
+                        &s[index..index + c.len_utf8()],
+                    ))
+                }
+            };
+            // value * 5 + digit, adding the digit before the last `value` so a prefix that
+            // overshoots by a digit or two near the limits doesn't count as overflow
+            value = value
+                .checked_mul(4)
+                .and_then(|times_four| times_four.checked_add(digit))
+                .and_then(|partial| partial.checked_add(value))
+                .ok_or_else(|| ParseError::at_token(ParseErrorKind::InvalidNumber, s, s))?;
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0");
+        }
+
+        // work on the magnitude and flip every digit for negative numbers
+        let (positive, negative) = ("012=-", "0-=21");
+        let digits = if self.0 > 0 { positive } else { negative };
+        let mut magnitude = self.0.unsigned_abs();
+        let mut reversed = vec![];
+
+        while magnitude > 0 {
+            let remainder = (magnitude % 5) as usize;
+            reversed.push(digits.as_bytes()[remainder] as char);
+            // 3 and 4 are written as 5 - 2 and 5 - 1, carrying into the next digit
+            magnitude = magnitude / 5 + u128::from(remainder > 2);
+        }
+
+        write!(f, "{}", reversed.iter().rev().collect::<String>())
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(value: i128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Snafu> for i128 {
+    fn from(snafu: Snafu) -> Self {
+        snafu.0
+    }
+}
+
+impl TryFrom<Snafu> for i64 {
+    type Error = TryFromIntError;
+
+    fn try_from(snafu: Snafu) -> Result<Self, Self::Error> {
+        i64::try_from(snafu.0)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Snafu(self.0 + rhs.0)
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+#[aoc_generator(day25)]
+fn input_generator(input: &str) -> Result<Vec<Snafu>, ParseError> {
+    parse_lines(25, input)
+}
+
+#[aoc(day25, part1)]
+fn part1(numbers: &[Snafu]) -> String {
+    numbers.iter().sum::<Snafu>().to_string()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Full of Hot Air";
+
+    type Parsed = Vec<Snafu>;
+    type Answer1 = String;
+    type Answer2 = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer1 {
+        part1(input)
+    }
+
+    /// There's no second puzzle on the last day: the final star comes with the other 49.
+    fn part2(_input: &Self::Parsed) -> Self::Answer2 {
+        "Merry Christmas!"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!("2=-1=0", part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_conversions() {
+        let examples: [(i64, &str); 15] = [
+            (1, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (6, "11"),
+            (7, "12"),
+            (8, "2="),
+            (9, "2-"),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ];
+        for (decimal, snafu) in examples {
+            assert_eq!(snafu, Snafu::from(decimal).to_string());
+            assert_eq!(Ok(decimal), i64::try_from(snafu.parse::<Snafu>().unwrap()));
+        }
+        assert_eq!("0", Snafu::default().to_string());
+        assert_eq!("-", Snafu::from(-1i64).to_string());
+        for extreme in [i128::MIN, i128::MAX] {
+            let snafu = Snafu::from(extreme);
+            assert_eq!(Ok(snafu), snafu.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator(&INPUT.replace("2=01", "2=31")).unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!((25, 5, 3), (error.day, error.line, error.column));
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(n in any::<i64>()) {
+            let snafu = Snafu::from(n);
+            prop_assert_eq!(Ok(snafu), snafu.to_string().parse::<Snafu>());
+            prop_assert_eq!(Ok(n), i64::try_from(snafu));
+        }
+
+        #[test]
+        fn test_round_trip_i128(n in any::<i128>()) {
+            let snafu = Snafu::from(n);
+            prop_assert_eq!(Ok(snafu), snafu.to_string().parse::<Snafu>());
+            prop_assert_eq!(n, i128::from(snafu));
+        }
+
+        #[test]
+        fn test_addition(a in any::<i64>(), b in any::<i64>()) {
+            let sum = Snafu::from(a) + Snafu::from(b);
+            prop_assert_eq!(a as i128 + b as i128, i128::from(sum));
+            prop_assert_eq!(sum, [Snafu::from(a), Snafu::from(b)].iter().sum::<Snafu>());
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
+use crate::error::{parse_lines, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
 fn priority_map() -> HashMap<char, i32> {
     let mut priorities = HashMap::new();
 
@@ -20,7 +23,7 @@ fn priority_map() -> HashMap<char, i32> {
 }
 
 #[derive(Debug, Clone)]
-struct Rucksack {
+pub struct Rucksack {
     first: HashSet<char>,
     second: HashSet<char>,
     full: HashSet<char>,
@@ -33,9 +36,18 @@ impl Rucksack {
 }
 
 impl FromStr for Rucksack {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // only letters have a priority
+        if let Some((index, c)) = s.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(ParseError::at_token(
+                ParseErrorKind::UnknownToken,
+                s,
+                &s[index..index + c.len_utf8()],
+            ));
+        }
+
         let split_index: usize = s.len() / 2;
         let first: HashSet<char> = s[..split_index].chars().collect();
         let second: HashSet<char> = s[split_index..].chars().collect();
@@ -49,11 +61,8 @@ impl FromStr for Rucksack {
 }
 
 #[aoc_generator(day3)]
-fn input_generator(input: &str) -> Vec<Rucksack> {
-    input
-        .lines()
-        .map(|ruck| Rucksack::from_str(ruck).unwrap())
-        .collect()
+fn input_generator(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    parse_lines(3, input)
 }
 
 #[aoc(day3, part1)]
@@ -90,6 +99,8 @@ fn part2(input: &[Rucksack]) -> i32 {
     sum
 }
 
+solution!(Day3, 3, "Rucksack Reorganization", Vec<Rucksack>, i32, i32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,13 +113,13 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(157, part1(&parsed_input))
     }
 
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(70, part2(&parsed_input))
     }
 }
@@ -1,5 +1,8 @@
 use std::{collections::HashSet, ops::Range, str::FromStr};
 
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
 #[derive(Debug)]
 struct Assignment {
     start: i32,
@@ -7,34 +10,37 @@ struct Assignment {
 }
 
 impl FromStr for Assignment {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once('-').unwrap();
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, s))?;
         Ok(Assignment {
-            start: start.parse::<i32>().unwrap(),
-            end: end.parse::<i32>().unwrap(),
+            start: parse_number(s, start)?,
+            end: parse_number(s, end)?,
         })
     }
 }
 
 #[derive(Debug)]
-struct Pair {
+pub struct Pair {
     first: Assignment,
     second: Assignment,
 }
 
 impl FromStr for Pair {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut pair = s
-            .split(',')
-            .map(|a| Assignment::from_str(a.trim()).unwrap());
+        let (first, second) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, s))?;
 
         Ok(Pair {
-            first: pair.next().unwrap(),
-            second: pair.next().unwrap(),
+            first: Assignment::from_str(first.trim())?,
+            second: Assignment::from_str(second.trim())
+                .map_err(|e| e.shift_column(first.len() + 1))?,
         })
     }
 }
@@ -76,8 +82,8 @@ impl Pair {
 }
 
 #[aoc_generator(day4)]
-fn input_generator(input: &str) -> Vec<Pair> {
-    input.lines().map(|l| Pair::from_str(l).unwrap()).collect()
+fn input_generator(input: &str) -> Result<Vec<Pair>, ParseError> {
+    parse_lines(4, input)
 }
 
 #[aoc(day4, part1)]
@@ -90,6 +96,8 @@ fn part2(input: &[Pair]) -> usize {
     input.iter().filter(|p| p.has_overlap()).count()
 }
 
+solution!(Day4, 4, "Camp Cleanup", Vec<Pair>, usize, usize);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,13 +110,20 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(2, part1(&parsed_input))
     }
 
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(4, part2(&parsed_input))
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator("2-4,6-8\n2-3,4-x").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumber, error.kind);
+        assert_eq!((4, 2, 7), (error.day, error.line, error.column));
+    }
 }
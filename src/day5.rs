@@ -1,68 +1,84 @@
 use std::collections::VecDeque;
 use std::{collections::BTreeMap, str::FromStr};
 
+use crate::error::{parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
 #[derive(Debug, Clone)]
-struct Container(char);
+pub struct Container(char);
 
 impl FromStr for Container {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let trimmed = s.trim();
-        if !trimmed.starts_with('[') && !trimmed.ends_with(']') {
-            return Err(());
+        let mut chars = trimmed.chars();
+        match (chars.next(), chars.next(), chars.next(), chars.next()) {
+            (Some('['), Some(c), Some(']'), None) => Ok(Self(c)),
+            _ => Err(ParseError::at_token(
+                ParseErrorKind::UnknownToken,
+                s,
+                trimmed,
+            )),
         }
-
-        let c = s.chars().nth(1).unwrap();
-        Ok(Self(c))
     }
 }
 
+pub type Stacks = BTreeMap<usize, VecDeque<Container>>;
+
 #[derive(Debug, Clone)]
-struct Instruction {
+pub struct Instruction {
     amount: i32,
     source: usize,
     dest: usize,
 }
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // I should probably do better validation of the string...
-        let parts: Vec<String> = s.split(' ').map(|p| p.to_string()).collect();
-        let amount = parts.get(1).map(|i| i.parse::<i32>().unwrap()).unwrap();
-        let source = parts.get(3).map(|s| s.parse::<usize>().unwrap()).unwrap();
-        let dest = parts.get(5).map(|s| s.parse::<usize>().unwrap()).unwrap();
+        let mut parts = s.split(' ');
+        let mut next_part = |keyword: Option<&str>| -> Result<&str, ParseError> {
+            let part = parts.next().ok_or_else(|| {
+                ParseError::at_token(ParseErrorKind::MissingToken, s, &s[s.len()..])
+            })?;
+            match keyword {
+                Some(keyword) if keyword != part => {
+                    Err(ParseError::at_token(ParseErrorKind::UnknownToken, s, part))
+                }
+                _ => Ok(part),
+            }
+        };
 
-        let instruction = Self {
+        next_part(Some("move"))?;
+        let amount = parse_number(s, next_part(None)?)?;
+        next_part(Some("from"))?;
+        let source = parse_number(s, next_part(None)?)?;
+        next_part(Some("to"))?;
+        let dest = parse_number(s, next_part(None)?)?;
+
+        Ok(Self {
             amount,
             source,
             dest,
-        };
-
-        //dbg!(&instruction);
-
-        Ok(instruction)
+        })
     }
 }
 
-fn build_container_map(s: &str) -> BTreeMap<usize, VecDeque<Container>> {
-    let mut container_map: BTreeMap<usize, VecDeque<Container>> = BTreeMap::new();
+fn build_container_map(s: &str) -> Result<Stacks, ParseError> {
+    let mut container_map: Stacks = BTreeMap::new();
     let lines: Vec<&str> = s.lines().collect();
 
-    let container_identifiers: Vec<usize> = lines
+    let identifier_line = lines
         .last()
-        .unwrap()
-        .split_ascii_whitespace()
-        .map(|c| c.parse::<usize>().unwrap())
-        .collect();
+        .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidLayout, s))?;
 
-    for c in container_identifiers {
-        container_map.insert(c, VecDeque::new());
+    for c in identifier_line.split_ascii_whitespace() {
+        let identifier = parse_number(identifier_line, c).map_err(|e| e.on_line(lines.len()))?;
+        container_map.insert(identifier, VecDeque::new());
     }
 
-    for line in &lines[..lines.len() - 1] {
+    for (line_index, line) in lines[..lines.len() - 1].iter().enumerate() {
         // substract 1 so we don't try to read the container identifier line
         let characters: Vec<char> = line.chars().collect();
         for (iteration, container_chars) in characters.chunks(4).enumerate() {
@@ -71,36 +87,61 @@ fn build_container_map(s: &str) -> BTreeMap<usize, VecDeque<Container>> {
             // no container at this level, keep going
             if container_string.trim().is_empty() {
                 continue;
-            } else {
-                let container = Container::from_str(&container_string).unwrap();
-                container_map
-                    .entry(iteration + 1)
-                    .and_modify(|containers| containers.push_back(container));
             }
+
+            let container = Container::from_str(&container_string)
+                .map_err(|e| e.on_line(line_index + 1).shift_column(iteration * 4))?;
+            container_map
+                .get_mut(&(iteration + 1))
+                .ok_or_else(|| {
+                    ParseError::new(ParseErrorKind::InvalidLayout, &container_string)
+                        .on_line(line_index + 1)
+                })?
+                .push_back(container);
         }
     }
 
-    container_map
+    Ok(container_map)
 }
 
-fn build_instruction_list(s: &str) -> Vec<Instruction> {
+fn build_instruction_list(s: &str, first_line: usize) -> Result<Vec<Instruction>, ParseError> {
     s.lines()
-        .map(|instruction| Instruction::from_str(instruction).unwrap())
+        .enumerate()
+        .map(|(index, instruction)| {
+            Instruction::from_str(instruction).map_err(|e| e.on_line(first_line + index))
+        })
         .collect()
 }
 
 #[aoc_generator(day5)]
-fn input_generator(input: &str) -> (BTreeMap<usize, VecDeque<Container>>, Vec<Instruction>) {
-    let (container_diagram, raw_instructions) = input.split_once("\n\n").unwrap();
-
-    let container_map = build_container_map(container_diagram);
-    let instructions = build_instruction_list(raw_instructions);
+fn input_generator(input: &str) -> Result<(Stacks, Vec<Instruction>), ParseError> {
+    let (container_diagram, raw_instructions) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidLayout, input).in_day(5))?;
+
+    let container_map = build_container_map(container_diagram).map_err(|e| e.in_day(5))?;
+
+    // instructions start after the diagram and the blank line separating them
+    let first_instruction_line = container_diagram.lines().count() + 2;
+    let instructions = build_instruction_list(raw_instructions, first_instruction_line)
+        .map_err(|e| e.in_day(5))?;
+
+    // make sure every instruction refers to stacks that exist
+    for (index, (line, instruction)) in raw_instructions.lines().zip(&instructions).enumerate() {
+        if !container_map.contains_key(&instruction.source)
+            || !container_map.contains_key(&instruction.dest)
+        {
+            return Err(ParseError::new(ParseErrorKind::InvalidLayout, line)
+                .in_day(5)
+                .on_line(first_instruction_line + index));
+        }
+    }
 
-    (container_map, instructions)
+    Ok((container_map, instructions))
 }
 
 #[aoc(day5, part1)]
-fn part1(input: &(BTreeMap<usize, VecDeque<Container>>, Vec<Instruction>)) -> String {
+fn part1(input: &(Stacks, Vec<Instruction>)) -> String {
     let mut map_and_instructions = input.to_owned();
     for instruction in map_and_instructions.1 {
         for _ in 1..=instruction.amount {
@@ -129,8 +170,8 @@ fn part1(input: &(BTreeMap<usize, VecDeque<Container>>, Vec<Instruction>)) -> St
 
     let top_containers: String = map_and_instructions
         .0
-        .iter()
-        .filter_map(|(_, stack)| stack.front())
+        .values()
+        .filter_map(|stack| stack.front())
         .map(|c: &Container| c.0.to_string())
         .collect();
 
@@ -138,7 +179,7 @@ fn part1(input: &(BTreeMap<usize, VecDeque<Container>>, Vec<Instruction>)) -> St
 }
 
 #[aoc(day5, part2)]
-fn part2(input: &(BTreeMap<usize, VecDeque<Container>>, Vec<Instruction>)) -> String {
+fn part2(input: &(Stacks, Vec<Instruction>)) -> String {
     let mut map_and_instructions = input.to_owned();
     for instruction in map_and_instructions.1 {
         let mut intermediary_stack: VecDeque<Container> = VecDeque::new();
@@ -175,14 +216,23 @@ fn part2(input: &(BTreeMap<usize, VecDeque<Container>>, Vec<Instruction>)) -> St
 
     let top_containers: String = map_and_instructions
         .0
-        .iter()
-        .filter_map(|(_, stack)| stack.front())
+        .values()
+        .filter_map(|stack| stack.front())
         .map(|c: &Container| c.0.to_string())
         .collect();
 
     top_containers
 }
 
+solution!(
+    Day5,
+    5,
+    "Supply Stacks",
+    (Stacks, Vec<Instruction>),
+    String,
+    String
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,13 +248,21 @@ move 1 from 1 to 2";
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!("CMZ", part1(&parsed_input))
     }
 
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!("MCD", part2(&parsed_input))
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("move 2 from 2 to 1", "move 2 from 2 too 1");
+        let error = input_generator(&input).unwrap_err();
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!((5, 8, 15), (error.day, error.line, error.column));
+    }
 }
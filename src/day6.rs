@@ -1,11 +1,25 @@
 use std::collections::HashSet;
 
-struct Packet(Vec<char>);
+use crate::error::{ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+pub struct Packet(Vec<char>);
 
 #[aoc_generator(day6)]
-fn input_generator(input: &str) -> Packet {
+fn input_generator(input: &str) -> Result<Packet, ParseError> {
+    let input = input.trim_end();
+    if let Some((index, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(ParseError::at_token(
+            ParseErrorKind::UnknownToken,
+            input,
+            &input[index..index + c.len_utf8()],
+        )
+        .in_day(6)
+        .on_line(1));
+    }
+
     let chars: Vec<char> = input.chars().collect();
-    Packet(chars)
+    Ok(Packet(chars))
 }
 
 #[aoc(day6, part1)]
@@ -30,67 +44,69 @@ fn part2(input: &Packet) -> usize {
     panic!("did not find packet marker!")
 }
 
+solution!(Day6, 6, "Tuning Trouble", Packet, usize, usize);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part1_a() {
-        let parsed_input = input_generator("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let parsed_input = input_generator("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
         assert_eq!(7, part1(&parsed_input))
     }
 
     #[test]
     fn test_part1_b() {
-        let parsed_input = input_generator("bvwbjplbgvbhsrlpgdmjqwftvncz");
+        let parsed_input = input_generator("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap();
         assert_eq!(5, part1(&parsed_input))
     }
 
     #[test]
     fn test_part1_c() {
-        let parsed_input = input_generator("nppdvjthqldpwncqszvftbrmjlhg");
+        let parsed_input = input_generator("nppdvjthqldpwncqszvftbrmjlhg").unwrap();
         assert_eq!(6, part1(&parsed_input))
     }
 
     #[test]
     fn test_part1_d() {
-        let parsed_input = input_generator("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
+        let parsed_input = input_generator("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap();
         assert_eq!(10, part1(&parsed_input))
     }
 
     #[test]
     fn test_part1_e() {
-        let parsed_input = input_generator("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
+        let parsed_input = input_generator("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap();
         assert_eq!(11, part1(&parsed_input))
     }
 
     #[test]
     fn test_part2_a() {
-        let parsed_input = input_generator("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let parsed_input = input_generator("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
         assert_eq!(19, part2(&parsed_input))
     }
 
     #[test]
     fn test_part2_b() {
-        let parsed_input = input_generator("bvwbjplbgvbhsrlpgdmjqwftvncz");
+        let parsed_input = input_generator("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap();
         assert_eq!(23, part2(&parsed_input))
     }
 
     #[test]
     fn test_part2_c() {
-        let parsed_input = input_generator("nppdvjthqldpwncqszvftbrmjlhg");
+        let parsed_input = input_generator("nppdvjthqldpwncqszvftbrmjlhg").unwrap();
         assert_eq!(23, part2(&parsed_input))
     }
 
     #[test]
     fn test_part2_d() {
-        let parsed_input = input_generator("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
+        let parsed_input = input_generator("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap();
         assert_eq!(29, part2(&parsed_input))
     }
 
     #[test]
     fn test_part2_e() {
-        let parsed_input = input_generator("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
+        let parsed_input = input_generator("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap();
         assert_eq!(26, part2(&parsed_input))
     }
 }
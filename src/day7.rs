@@ -1,36 +1,48 @@
 use std::str::FromStr;
 
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
 #[derive(Debug)]
-enum Line {
+pub enum Line {
     CommandInput(Command),
     CommandOutput(FileSystemObject),
 }
 
-impl From<&str> for Line {
-    fn from(s: &str) -> Self {
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.starts_with('$') {
-            Line::CommandInput(Command::from_str(s).unwrap())
+            Ok(Line::CommandInput(Command::from_str(s)?))
         } else {
-            Line::CommandOutput(FileSystemObject::from_str(s).unwrap())
+            Ok(Line::CommandOutput(FileSystemObject::from_str(s)?))
         }
     }
 }
 
 #[derive(Debug)]
-enum Command {
+pub enum Command {
     ChangeDirectory(String),
     List,
 }
 
 impl FromStr for Command {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let missing = || ParseError::at_token(ParseErrorKind::MissingToken, s, &s[s.len()..]);
+
         let command_parts: Vec<_> = s.split(' ').collect();
-        let command = match command_parts[1] {
-            "cd" => Command::ChangeDirectory(command_parts[2].to_string()),
-            "ls" => Command::List,
-            _ => return Err(format!("Unknown command line: {}", s)),
+        let command = match command_parts.get(1) {
+            Some(&"cd") => {
+                Command::ChangeDirectory(command_parts.get(2).ok_or_else(missing)?.to_string())
+            }
+            Some(&"ls") => Command::List,
+            Some(other) => {
+                return Err(ParseError::at_token(ParseErrorKind::UnknownToken, s, other))
+            }
+            None => return Err(missing()),
         };
         Ok(command)
     }
@@ -48,7 +60,7 @@ impl From<usize> for NodeId {
 }
 
 #[derive(Debug, Clone)]
-struct FileSystemObject {
+pub struct FileSystemObject {
     _name: String,
     _type: FileSystemObjectType,
     parent: Option<NodeId>,
@@ -57,11 +69,13 @@ struct FileSystemObject {
 }
 
 impl FromStr for FileSystemObject {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let missing = || ParseError::at_token(ParseErrorKind::MissingToken, s, &s[s.len()..]);
+
         if s.starts_with("dir") {
-            let (_, directory_name) = s.split_once(' ').unwrap();
+            let (_, directory_name) = s.split_once(' ').ok_or_else(missing)?;
             Ok(FileSystemObject {
                 _name: directory_name.to_string(),
                 _type: FileSystemObjectType::Directory,
@@ -70,12 +84,12 @@ impl FromStr for FileSystemObject {
                 children: vec![],
             })
         } else {
-            let (file_size, file_name) = s.split_once(' ').unwrap();
+            let (file_size, file_name) = s.split_once(' ').ok_or_else(missing)?;
             Ok(FileSystemObject {
                 _name: file_name.to_string(),
                 _type: FileSystemObjectType::File,
                 parent: None,
-                size: file_size.parse::<i32>().unwrap(),
+                size: parse_number(s, file_size)?,
                 children: vec![],
             })
         }
@@ -187,9 +201,19 @@ fn build_filesystem(lines: &[Line]) -> FileSystem {
 }
 
 #[aoc_generator(day7)]
-fn input_generator(input: &str) -> Vec<Line> {
-    let lines = input.lines().map(|line| line.into()).collect();
-    lines
+fn input_generator(input: &str) -> Result<Vec<Line>, ParseError> {
+    let lines: Vec<Line> = parse_lines(7, input)?;
+
+    // `build_filesystem` needs to start from the root directory
+    match lines.first() {
+        Some(Line::CommandInput(Command::ChangeDirectory(_))) => Ok(lines),
+        _ => Err(ParseError::new(
+            ParseErrorKind::InvalidLayout,
+            input.lines().next().unwrap_or_default(),
+        )
+        .in_day(7)
+        .on_line(1)),
+    }
 }
 
 #[aoc(day7, part1)]
@@ -235,6 +259,8 @@ fn part2(input: &[Line]) -> i32 {
     panic!("oh no! upgrade isn't possible");
 }
 
+solution!(Day7, 7, "No Space Left On Device", Vec<Line>, i32, i32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,13 +290,20 @@ $ ls
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(95437, part1(&parsed_input))
     }
 
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(24933642, part2(&parsed_input))
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = input_generator("$ cd /\n$ ls\n12k b.txt").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumber, error.kind);
+        assert_eq!((7, 3, 1), (error.day, error.line, error.column));
+    }
 }
@@ -1,155 +1,83 @@
+use std::fmt;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::{Direction, Grid, Position};
+use crate::solution::solution;
+
 #[derive(Debug, Clone)]
 struct Tree(u8);
 
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Debug)]
-struct Map {
-    trees: Vec<Tree>,
-    max_row: usize,
-    max_col: usize,
+pub struct Map {
+    trees: Grid<Tree>,
 }
 
 impl Map {
-    fn is_visible(&self, tree_index: usize) -> (bool, usize) {
-        //dbg!(&tree_index);
-        let tree: &Tree = &self.trees[tree_index];
-        let num_of_trees = self.trees.len();
-
-        let column_index = tree_index % self.max_col;
-        let row_index = tree_index / self.max_row;
-
-        // [down, up, left, right]
-        let mut visibility_scores: [usize; 4] = [0, 0, 0, 0];
-        let mut visibility_score = 0;
-
-        // we are at an edge; which is always visible
-        if column_index == 0 // left most column
-            || column_index == self.max_col - 1 // right most column
-            || row_index == 0  // first row
-            || row_index == self.max_row - 1
-        {
-            return (true, visibility_score);
-        }
-
-        // check column down
-        let mut next_index = tree_index + self.max_col;
-        let mut visible_down = true;
-        while next_index < num_of_trees {
-            visibility_scores[0] += 1;
-            if self.trees[next_index].0 >= tree.0 {
-                visible_down = false;
-                break;
-            }
-            next_index += self.max_col;
-        }
-
-        // check column up
-        let mut visible_up = true;
-        next_index = tree_index - self.max_col;
-        while next_index > 0 {
-            visibility_scores[1] += 1;
-            if self.trees[next_index].0 >= tree.0 {
-                visible_up = false;
-
-                break;
+    /// Whether the tree at `position` can be seen from outside the forest, and its scenic score.
+    fn is_visible(&self, position: Position) -> (bool, usize) {
+        let tree = &self.trees[position];
+
+        let mut visible = false;
+        let mut visibility_score = 1;
+
+        for direction in Direction::CARDINAL {
+            let mut viewing_distance = 0;
+            let mut blocked = false;
+
+            for (_, other_tree) in self.trees.ray(position, direction) {
+                viewing_distance += 1;
+                if other_tree.0 >= tree.0 {
+                    blocked = true;
+                    break;
+                }
             }
 
-            // next_index -= self.max_col;
-            next_index = match next_index.checked_sub(self.max_col) {
-                Some(i) => i,
-                None => break,
-            };
+            // trees on the edge see nothing in at least one direction, so they score 0
+            visible |= !blocked;
+            visibility_score *= viewing_distance;
         }
 
-        // check row left
-        let mut visible_left = true;
-        let mut left_trees = self.trees[tree_index - column_index..tree_index].to_vec();
-        left_trees.reverse();
-
-        for (_, prev_tree) in left_trees.iter().enumerate() {
-            visibility_scores[2] += 1;
-            if prev_tree.0 >= tree.0 {
-                visible_left = false;
-                break;
-            }
-        }
-
-        // check row right
-        let right_bound = (self.max_col - 1) - column_index;
-        let mut visible_right = true;
-        for (_, next_tree) in self.trees[tree_index + 1..=tree_index + right_bound]
-            .iter()
-            .enumerate()
-        {
-            visibility_scores[3] += 1;
-            if next_tree.0 >= tree.0 {
-                visible_right = false;
-                break;
-            }
-        }
-
-        visibility_score = visibility_scores.iter().product();
-
-        // dbg!(tree_index);
-        // dbg!(&visibility_scores);
-
-        (
-            visible_up || visible_down || visible_left || visible_right,
-            visibility_score,
-        )
+        (visible, visibility_score)
     }
 }
 
 #[aoc_generator(day8)]
-fn input_generator(input: &str) -> Map {
-    let max_row: usize = input.lines().count();
-    let mut max_col: usize = 0;
-
-    let trees: Vec<Tree> = input
-        .lines()
-        .flat_map(|line| {
-            if max_col == 0 {
-                max_col = line.chars().count();
-            }
-
-            line.chars()
-                .map(|c| Tree(c.to_digit(10).unwrap().try_into().unwrap()))
-                .collect::<Vec<Tree>>()
-        })
-        .collect();
-
-    Map {
-        trees,
-        max_row,
-        max_col,
-    }
+fn input_generator(input: &str) -> Result<Map, ParseError> {
+    let trees = Grid::parse(input, |c| {
+        c.to_digit(10)
+            .map(|height| Tree(height as u8))
+            .ok_or(ParseErrorKind::InvalidNumber)
+    })
+    .map_err(|e| e.in_day(8))?;
+
+    Ok(Map { trees })
 }
 
 #[aoc(day8, part1)]
 fn part1(map: &Map) -> usize {
-    // dbg!(&map);
-    let mut visible_trees_total = 0;
-
-    for (index, _) in map.trees.iter().enumerate() {
-        if map.is_visible(index).0 {
-            visible_trees_total += 1;
-        }
-    }
-
-    visible_trees_total
+    map.trees
+        .positions()
+        .filter(|&position| map.is_visible(position).0)
+        .count()
 }
 
 #[aoc(day8, part2)]
 fn part2(map: &Map) -> usize {
-    let scores: Vec<usize> = map
-        .trees
-        .iter()
-        .enumerate()
-        .map(|(index, _tree)| map.is_visible(index).1)
-        .collect();
-
-    *scores.iter().max().unwrap()
+    map.trees
+        .positions()
+        .map(|position| map.is_visible(position).1)
+        .max()
+        .unwrap_or_default()
 }
 
+solution!(Day8, 8, "Treetop Tree House", Map, usize, usize);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,13 +89,46 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(21, part1(&parsed_input))
     }
 
     #[test]
     fn test_part2() {
-        let parsed_input = input_generator(INPUT);
+        let parsed_input = input_generator(INPUT).unwrap();
         assert_eq!(8, part2(&parsed_input))
     }
+
+    #[test]
+    fn test_wide_map() {
+        let parsed_input = input_generator("22222\n21312\n22222").unwrap();
+        assert_eq!(13, part1(&parsed_input));
+        assert_eq!(4, part2(&parsed_input));
+
+        // the first three rows of the example
+        let parsed_input = input_generator("30373\n25512\n65332").unwrap();
+        assert_eq!(14, part1(&parsed_input));
+        assert_eq!(2, part2(&parsed_input));
+    }
+
+    #[test]
+    fn test_tall_map() {
+        let parsed_input = input_generator("222\n212\n232\n212\n222").unwrap();
+        assert_eq!(13, part1(&parsed_input));
+        assert_eq!(4, part2(&parsed_input));
+    }
+
+    #[test]
+    fn test_single_row() {
+        let parsed_input = input_generator("12321").unwrap();
+        assert_eq!(5, part1(&parsed_input));
+        assert_eq!(0, part2(&parsed_input));
+    }
+
+    #[test]
+    fn test_single_column() {
+        let parsed_input = input_generator("1\n2\n3").unwrap();
+        assert_eq!(3, part1(&parsed_input));
+        assert_eq!(0, part2(&parsed_input));
+    }
 }
@@ -0,0 +1,170 @@
+use std::{collections::HashSet, str::FromStr};
+
+use crate::error::{parse_lines, parse_number, ParseError, ParseErrorKind};
+use crate::solution::solution;
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let direction = match s {
+            "U" => Self::Up,
+            "D" => Self::Down,
+            "L" => Self::Left,
+            "R" => Self::Right,
+            _ => return Err(ParseError::at_token(ParseErrorKind::UnknownToken, s, s)),
+        };
+
+        Ok(direction)
+    }
+}
+
+impl Direction {
+    fn offset(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Motion {
+    direction: Direction,
+    steps: u32,
+}
+
+impl FromStr for Motion {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, steps) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at_token(ParseErrorKind::MissingToken, s, s))?;
+
+        Ok(Motion {
+            direction: Direction::from_str(direction)?,
+            steps: parse_number(s, steps)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Rope {
+    // knots[0] is the head, the last knot is the tail
+    knots: Vec<(i32, i32)>,
+    tail_visited: HashSet<(i32, i32)>,
+}
+
+impl Rope {
+    fn new(num_of_knots: usize) -> Self {
+        assert!(num_of_knots > 0, "a rope needs at least one knot");
+
+        Self {
+            knots: vec![(0, 0); num_of_knots],
+            tail_visited: HashSet::from([(0, 0)]),
+        }
+    }
+
+    fn apply(&mut self, motion: &Motion) {
+        let (dx, dy) = motion.direction.offset();
+
+        for _ in 0..motion.steps {
+            self.knots[0].0 += dx;
+            self.knots[0].1 += dy;
+
+            for i in 1..self.knots.len() {
+                let (leader_x, leader_y) = self.knots[i - 1];
+                let knot = &mut self.knots[i];
+                let (gap_x, gap_y) = (leader_x - knot.0, leader_y - knot.1);
+
+                // still touching (including diagonally), so this knot and the ones behind it stay put
+                if gap_x.abs() <= 1 && gap_y.abs() <= 1 {
+                    break;
+                }
+
+                knot.0 += gap_x.signum();
+                knot.1 += gap_y.signum();
+            }
+
+            self.tail_visited.insert(*self.knots.last().unwrap());
+        }
+    }
+}
+
+fn simulate(motions: &[Motion], num_of_knots: usize) -> usize {
+    let mut rope = Rope::new(num_of_knots);
+
+    for motion in motions {
+        rope.apply(motion);
+    }
+
+    rope.tail_visited.len()
+}
+
+#[aoc_generator(day9)]
+fn input_generator(input: &str) -> Result<Vec<Motion>, ParseError> {
+    parse_lines(9, input)
+}
+
+#[aoc(day9, part1)]
+fn part1(input: &[Motion]) -> usize {
+    simulate(input, 2)
+}
+
+#[aoc(day9, part2)]
+fn part2(input: &[Motion]) -> usize {
+    simulate(input, 10)
+}
+
+solution!(Day9, 9, "Rope Bridge", Vec<Motion>, usize, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    const LARGER_INPUT: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+    #[test]
+    fn test_part1() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(13, part1(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed_input = input_generator(INPUT).unwrap();
+        assert_eq!(1, part2(&parsed_input))
+    }
+
+    #[test]
+    fn test_part2_larger() {
+        let parsed_input = input_generator(LARGER_INPUT).unwrap();
+        assert_eq!(36, part2(&parsed_input))
+    }
+}
@@ -0,0 +1,195 @@
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A token that should have been a number wasn't one.
+    InvalidNumber,
+    /// A token we don't know how to interpret (bad keyword, shape, character...).
+    UnknownToken,
+    /// Something we expected on the line wasn't there.
+    MissingToken,
+    /// The overall shape of the input is wrong (missing sections, ragged grids...).
+    InvalidLayout,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            ParseErrorKind::InvalidNumber => "invalid number",
+            ParseErrorKind::UnknownToken => "unknown token",
+            ParseErrorKind::MissingToken => "missing token",
+            ParseErrorKind::InvalidLayout => "invalid layout",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// Error returned by every input generator.
+///
+/// `line` and `column` are 1-based; a value of 0 means the position is unknown,
+/// e.g. a `FromStr` impl that hasn't been told which line it was parsing yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, text: &str) -> Self {
+        Self {
+            kind,
+            day: 0,
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+        }
+    }
+
+    /// Builds an error pointing at `token`, which must be a sub-slice of `source`.
+    /// The column is worked out from where the token sits inside the source.
+    pub fn at_token(kind: ParseErrorKind, source: &str, token: &str) -> Self {
+        let offset = (token.as_ptr() as usize).saturating_sub(source.as_ptr() as usize);
+        let column = source[..offset.min(source.len())].chars().count() + 1;
+
+        Self {
+            column,
+            ..Self::new(kind, token)
+        }
+    }
+
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day = day;
+        self
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Shifts the column, for when a `FromStr` impl was handed part of a line.
+    pub fn shift_column(mut self, offset: usize) -> Self {
+        if self.column > 0 {
+            self.column += offset;
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.day > 0 {
+            write!(f, "day {}, ", self.day)?;
+        }
+        if self.line > 0 {
+            write!(f, "line {}, ", self.line)?;
+        }
+        if self.column > 0 {
+            write!(f, "column {}, ", self.column)?;
+        }
+        write!(f, "{}: {:?}", self.kind, self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token` (a sub-slice of `source`) as a number.
+pub fn parse_number<T: FromStr>(source: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at_token(ParseErrorKind::InvalidNumber, source, token))
+}
+
+/// Parses every line of `input` with `T::from_str`, tagging errors with the day and line number.
+pub fn parse_lines<T>(day: u8, input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| T::from_str(line).map_err(|e| e.in_day(day).on_line(index + 1)))
+        .collect()
+}
+
+/// Splits `input` into blank-line separated blocks, each with the (1-based) line of the
+/// whole input it starts on.
+pub fn blocks(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut first_line = 1;
+    input.split("\n\n").map(move |block| {
+        let start = first_line;
+        first_line += block.lines().count() + 1;
+        (start, block)
+    })
+}
+
+/// Parses blank-line separated blocks of `input` with `T::from_str`.
+///
+/// `T` reports line numbers relative to its own block (or 0 if it doesn't know);
+/// they're shifted to lines of the whole input here.
+pub fn parse_blocks<T>(day: u8, input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    blocks(input)
+        .map(|(first_line, block)| {
+            T::from_str(block).map_err(|e| {
+                let line = first_line + e.line.saturating_sub(1);
+                e.in_day(day).on_line(line)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_token_column() {
+        let source = "move 1 from x to 3";
+        let token = &source[12..13];
+        let error = ParseError::at_token(ParseErrorKind::InvalidNumber, source, token);
+        assert_eq!(13, error.column);
+        assert_eq!("x", error.text);
+    }
+
+    #[test]
+    fn test_display() {
+        let source = "A Q";
+        let error = ParseError::at_token(ParseErrorKind::UnknownToken, source, &source[2..])
+            .in_day(2)
+            .on_line(7);
+        assert_eq!(
+            "day 2, line 7, column 3, unknown token: \"Q\"",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let error = parse_lines::<Number>(1, "1\n2\nthree").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumber, error.kind);
+        assert_eq!((1, 3, 1), (error.day, error.line, error.column));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let blocks: Vec<(usize, &str)> = blocks("a\nb\n\nc\n\nd\ne\nf").collect();
+        assert_eq!(vec![(1, "a\nb"), (4, "c"), (6, "d\ne\nf")], blocks);
+    }
+
+    #[derive(Debug)]
+    struct Number;
+
+    impl FromStr for Number {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            parse_number::<i32>(s, s).map(|_| Number)
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::{ParseError, ParseErrorKind};
+
+/// `(row, col)`, with `(0, 0)` in the top left corner.
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// `(row, col)` step taken when moving one cell in this direction.
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+/// A rectangular grid stored row by row in a flat `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells laid out row by row. Returns `None` if the number of
+    /// cells doesn't match the dimensions.
+    pub fn from_cells(rows: usize, cols: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == rows * cols).then_some(Self { cells, rows, cols })
+    }
+
+    /// Parses a character grid, one row per line, converting each character with `parse_cell`.
+    ///
+    /// Errors carry the line and column of the offending character; ragged rows are
+    /// reported as [`ParseErrorKind::InvalidLayout`].
+    pub fn parse(
+        input: &str,
+        mut parse_cell: impl FnMut(char) -> Result<T, ParseErrorKind>,
+    ) -> Result<Self, ParseError> {
+        let cols = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = vec![];
+        let mut rows = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            if line.chars().count() != cols {
+                return Err(ParseError::new(ParseErrorKind::InvalidLayout, line).on_line(row + 1));
+            }
+
+            for (index, c) in line.char_indices() {
+                let cell = parse_cell(c).map_err(|kind| {
+                    ParseError::at_token(kind, line, &line[index..index + c.len_utf8()])
+                        .on_line(row + 1)
+                })?;
+                cells.push(cell);
+            }
+            rows += 1;
+        }
+
+        Ok(Self { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.cols + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.cols + position.1])
+        } else {
+            None
+        }
+    }
+
+    /// The neighbouring position in `direction`, if it's still inside the grid.
+    pub fn step(&self, (row, col): Position, direction: Direction) -> Option<Position> {
+        let (d_row, d_col) = direction.offset();
+        let next = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(next).then_some(next)
+    }
+
+    /// Positions above, below, left and right of `position` that are inside the grid.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// Like [`Grid::neighbors4`], including diagonals.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// Walks from `position` (exclusive) in `direction` until the edge of the grid.
+    pub fn ray(
+        &self,
+        position: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = (Position, &T)> + '_ {
+        std::iter::successors(self.step(position, direction), move |&p| {
+            self.step(p, direction)
+        })
+        .map(|p| (p, &self[p]))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} is outside the grid", row);
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.cols, "column {} is outside the grid", col);
+        self.cells.iter().skip(col).step_by(self.cols.max(1))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell (row by row) matching `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(row) {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = "abc
+def";
+
+    fn parse(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = parse(INPUT);
+        assert_eq!((2, 3), (grid.rows(), grid.cols()));
+        assert_eq!('f', grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(INPUT, grid.to_string());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse("ab\nc", Ok).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidLayout, error.kind);
+        assert_eq!(2, error.line);
+
+        let error = Grid::<u32>::parse("12\n3x", |c| {
+            c.to_digit(10).ok_or(ParseErrorKind::InvalidNumber)
+        })
+        .unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = parse(INPUT);
+        let mut corner: Vec<Position> = grid.neighbors4((0, 0)).collect();
+        corner.sort();
+        assert_eq!(vec![(0, 1), (1, 0)], corner);
+        assert_eq!(5, grid.neighbors8((0, 1)).count());
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = parse(INPUT);
+        let right: Vec<char> = grid
+            .ray((0, 0), Direction::Right)
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(vec!['b', 'c'], right);
+        assert_eq!(0, grid.ray((0, 0), Direction::Up).count());
+        assert_eq!(
+            vec!['e'],
+            grid.ray((0, 0), Direction::DownRight)
+                .map(|(_, c)| *c)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_row_and_column() {
+        let grid = parse(INPUT);
+        assert_eq!(&['d', 'e', 'f'], grid.row(1));
+        assert_eq!(vec![&'b', &'e'], grid.column(1).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside the grid")]
+    fn test_row_out_of_bounds() {
+        parse(INPUT).row(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside the grid")]
+    fn test_column_out_of_bounds() {
+        let _ = parse(INPUT).column(3);
+    }
+}
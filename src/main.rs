@@ -1 +1,734 @@
-aoc_runner_derive::aoc_main! { lib = advent_of_code_2022 }
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use advent_of_code_2022::{
+    answers::{self, Answers, Verdict},
+    profile::{self, CountingAllocator},
+    runner::{self, Part, RunError, Variant},
+    solution,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the selected days and print the answers
+    Run {
+        #[command(flatten)]
+        selection: Selection,
+        /// Print parse/part timings and peak memory per day instead of the answers
+        #[arg(long)]
+        report: bool,
+    },
+    /// Time the selected days over several iterations
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+        /// Number of times each part is solved
+        #[arg(long, default_value_t = 10)]
+        iterations: u32,
+    },
+    /// List the implemented days and their default inputs
+    List {
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Check the selected days against the recorded answers
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+        /// Answers manifest to compare against
+        #[arg(long, default_value_os_t = answers::answers_path())]
+        answers: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct Selection {
+    /// Only run this day
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Only run this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Read the puzzle input from this file instead of input/2022/dayN.txt
+    #[arg(long, requires = "day", conflicts_with_all = ["variant", "all_variants"])]
+    input: Option<PathBuf>,
+    /// Use input/2022/dayN_<VARIANT>.txt ("real" is dayN.txt)
+    #[arg(long, conflicts_with = "all_variants")]
+    variant: Option<String>,
+    /// Run every input variant found for each day
+    #[arg(long)]
+    all_variants: bool,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// One day/part to solve, and where its input comes from.
+struct Task {
+    day: u8,
+    part: Part,
+    variant: String,
+    input: PathBuf,
+}
+
+impl Selection {
+    fn days(&self) -> Vec<u8> {
+        match self.day {
+            Some(day) => vec![day],
+            None => solution::registry()
+                .iter()
+                .map(|puzzle| puzzle.day)
+                .collect(),
+        }
+    }
+
+    /// Without `--day`, days whose input isn't on disk are skipped rather than failed, so
+    /// every subcommand covers just the inputs that are checked in. An explicit `--day`
+    /// still reports the missing file.
+    fn skips(&self, input: &Path) -> bool {
+        self.day.is_none() && !input.exists()
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(1) => vec![Part::One],
+            Some(_) => vec![Part::Two],
+            None => Part::ALL.to_vec(),
+        }
+    }
+
+    fn tasks(&self) -> Vec<Task> {
+        let parts = self.parts();
+
+        self.days()
+            .iter()
+            .flat_map(|&day| {
+                let variants = self.variants(day);
+                parts.iter().flat_map(move |&part| {
+                    variants.clone().into_iter().map(move |variant| Task {
+                        day,
+                        part,
+                        variant: variant.name,
+                        input: variant.path,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn variants(&self, day: u8) -> Vec<Variant> {
+        if let Some(input) = &self.input {
+            return vec![Variant {
+                name: answers::input_key(input),
+                path: input.clone(),
+            }];
+        }
+
+        let name = self.variant.as_deref().unwrap_or(runner::REAL_VARIANT);
+        let requested = Variant {
+            name: name.to_string(),
+            path: runner::variant_path(day, name),
+        };
+
+        if self.all_variants {
+            let variants = runner::variants(day);
+            // fall back to the real input so a missing file is still reported
+            if !variants.is_empty() {
+                return variants;
+            }
+        }
+
+        vec![requested]
+    }
+}
+
+impl Task {
+    fn solve(&self) -> Result<String, RunError> {
+        let input = runner::read_input(&self.input)?;
+        runner::solve(self.day, self.part, &input)
+    }
+}
+
+fn label(day: u8, part: u8, variant: &str) -> String {
+    if variant == runner::REAL_VARIANT {
+        format!("Day {} - Part {}", day, part)
+    } else {
+        format!("Day {} - Part {} ({})", day, part, variant)
+    }
+}
+
+#[derive(Serialize)]
+struct Outcome {
+    day: u8,
+    part: u8,
+    variant: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    skipped: bool,
+}
+
+impl Outcome {
+    fn new(task: &Task, result: Result<String, RunError>) -> Self {
+        let (answer, error) = match result {
+            Ok(answer) => (Some(answer), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        Self {
+            day: task.day,
+            part: task.part.number(),
+            variant: task.variant.clone(),
+            answer,
+            error,
+            skipped: false,
+        }
+    }
+
+    /// A task left out because its input doesn't exist.
+    fn skipped(task: &Task) -> Self {
+        Self {
+            day: task.day,
+            part: task.part.number(),
+            variant: task.variant.clone(),
+            answer: None,
+            error: None,
+            skipped: true,
+        }
+    }
+
+    fn label(&self) -> String {
+        label(self.day, self.part, &self.variant)
+    }
+}
+
+#[derive(Serialize)]
+struct Timing {
+    day: u8,
+    part: u8,
+    iterations: u32,
+    mean_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).expect("serializable output")
+    );
+}
+
+/// Prints one row per day and variant, with a column per part. Multi-line answers
+/// (and errors) don't fit in a cell, so they're printed underneath the table.
+fn print_variant_table(outcomes: &[Outcome]) {
+    let mut rows: Vec<(u8, &str, [String; 2])> = vec![];
+    let mut details = vec![];
+
+    for outcome in outcomes {
+        let cell = match (&outcome.answer, &outcome.error) {
+            (Some(answer), _) if answer.contains('\n') => {
+                details.push(format!(
+                    "{}:\n{}",
+                    outcome.label(),
+                    answer.trim_matches('\n')
+                ));
+                "(see below)".to_string()
+            }
+            (Some(answer), _) => answer.clone(),
+            (None, None) => "skipped".to_string(),
+            (_, error) => {
+                details.push(format!(
+                    "{}: FAILED: {}",
+                    outcome.label(),
+                    error.as_deref().unwrap_or_default()
+                ));
+                "FAILED".to_string()
+            }
+        };
+
+        let column = outcome.part as usize - 1;
+        match rows
+            .iter_mut()
+            .find(|(day, variant, _)| *day == outcome.day && *variant == outcome.variant)
+        {
+            Some(row) => row.2[column] = cell,
+            None => {
+                let mut cells = [String::new(), String::new()];
+                cells[column] = cell;
+                rows.push((outcome.day, &outcome.variant, cells));
+            }
+        }
+    }
+
+    let variant_width = rows.iter().map(|r| r.1.len()).max().unwrap_or(0).max(7);
+    let part1_width = rows.iter().map(|r| r.2[0].len()).max().unwrap_or(0).max(6);
+
+    println!(
+        "{:>3}  {:<variant_width$}  {:<part1_width$}  Part 2",
+        "Day", "Variant", "Part 1"
+    );
+    for (day, variant, [part1, part2]) in &rows {
+        println!(
+            "{:>3}  {:<variant_width$}  {:<part1_width$}  {}",
+            day, variant, part1, part2
+        );
+    }
+
+    for detail in details {
+        println!("\n{}", detail);
+    }
+}
+
+fn run(selection: &Selection) -> bool {
+    let outcomes: Vec<Outcome> = selection
+        .tasks()
+        .iter()
+        .map(|task| {
+            if selection.skips(&task.input) {
+                Outcome::skipped(task)
+            } else {
+                Outcome::new(task, task.solve())
+            }
+        })
+        .collect();
+
+    match selection.format {
+        Format::Text if selection.all_variants => print_variant_table(&outcomes),
+        Format::Text => {
+            for outcome in &outcomes {
+                match (&outcome.answer, &outcome.error) {
+                    (Some(answer), _) => println!("{}: {}", outcome.label(), answer),
+                    (_, Some(error)) => eprintln!("{}: FAILED: {}", outcome.label(), error),
+                    (None, None) => eprintln!("{}: skipped, no input", outcome.label()),
+                }
+            }
+        }
+        Format::Json => print_json(&outcomes),
+    }
+
+    outcomes.iter().all(|outcome| outcome.error.is_none())
+}
+
+#[derive(Serialize)]
+struct ReportRow {
+    day: u8,
+    variant: String,
+    parse_ms: f64,
+    part1_ms: Option<f64>,
+    part2_ms: Option<f64>,
+    peak_bytes: usize,
+}
+
+fn format_ms(ms: Option<f64>) -> String {
+    ms.map_or_else(|| "-".to_string(), |ms| format!("{:.3}", ms))
+}
+
+fn format_bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
+
+fn report(selection: &Selection) -> bool {
+    let parts = selection.parts();
+    let mut rows = vec![];
+    let mut failures = vec![];
+    let mut skipped = vec![];
+
+    for day in selection.days() {
+        for variant in selection.variants(day) {
+            if selection.skips(&variant.path) {
+                skipped.push(format!("Day {} ({}): skipped, no input", day, variant.name));
+                continue;
+            }
+
+            let result = solution::find(day)
+                .ok_or(RunError::UnknownDay(day))
+                .and_then(|puzzle| {
+                    let input = runner::read_input(&variant.path)?;
+                    profile::profile(puzzle, &input, &parts)
+                });
+
+            match result {
+                Ok(profile) => rows.push(ReportRow {
+                    day,
+                    variant: variant.name,
+                    parse_ms: millis(profile.parse),
+                    part1_ms: profile.part1.map(millis),
+                    part2_ms: profile.part2.map(millis),
+                    peak_bytes: profile.peak_bytes,
+                }),
+                Err(e) => failures.push(format!("Day {} ({}): FAILED: {}", day, variant.name, e)),
+            }
+        }
+    }
+
+    let sum = |ms: fn(&ReportRow) -> Option<f64>| -> Option<f64> {
+        rows.iter().filter_map(ms).reduce(|a, b| a + b)
+    };
+    let total = ReportRow {
+        day: 0,
+        variant: "total".to_string(),
+        parse_ms: sum(|row| Some(row.parse_ms)).unwrap_or_default(),
+        part1_ms: sum(|row| row.part1_ms),
+        part2_ms: sum(|row| row.part2_ms),
+        // peaks don't add up, the total is the worst single day
+        peak_bytes: rows
+            .iter()
+            .map(|row| row.peak_bytes)
+            .max()
+            .unwrap_or_default(),
+    };
+
+    match selection.format {
+        Format::Text => {
+            println!(
+                "{:>3}  {:<8} {:>12} {:>12} {:>12} {:>12}",
+                "Day", "Variant", "Parse (ms)", "Part 1 (ms)", "Part 2 (ms)", "Peak memory"
+            );
+            for row in rows.iter().chain([&total]) {
+                let day = if row.day == 0 {
+                    String::new()
+                } else {
+                    row.day.to_string()
+                };
+                println!(
+                    "{:>3}  {:<8} {:>12.3} {:>12} {:>12} {:>12}",
+                    day,
+                    row.variant,
+                    row.parse_ms,
+                    format_ms(row.part1_ms),
+                    format_ms(row.part2_ms),
+                    format_bytes(row.peak_bytes)
+                );
+            }
+            for line in skipped.iter().chain(&failures) {
+                eprintln!("{}", line);
+            }
+        }
+        Format::Json => print_json(&serde_json::json!({
+            "days": rows,
+            "total": {
+                "parse_ms": total.parse_ms,
+                "part1_ms": total.part1_ms,
+                "part2_ms": total.part2_ms,
+                "peak_bytes": total.peak_bytes,
+            },
+            "failures": failures,
+            "skipped": skipped,
+        })),
+    }
+
+    failures.is_empty()
+}
+
+fn bench(selection: &Selection, iterations: u32) -> bool {
+    let iterations = iterations.max(1);
+    let mut timings = vec![];
+    let mut failures = vec![];
+    let mut skipped = vec![];
+
+    for task in selection.tasks() {
+        if selection.skips(&task.input) {
+            skipped.push(Outcome::skipped(&task));
+            continue;
+        }
+
+        let input = match runner::read_input(&task.input) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(Outcome::new(&task, Err(e)));
+                continue;
+            }
+        };
+
+        let mut samples = Vec::with_capacity(iterations as usize);
+        let mut failed = None;
+        for _ in 0..iterations {
+            let start = Instant::now();
+            if let Err(e) = runner::solve(task.day, task.part, &input) {
+                failed = Some(e);
+                break;
+            }
+            samples.push(start.elapsed());
+        }
+
+        if let Some(e) = failed {
+            failures.push(Outcome::new(&task, Err(e)));
+            continue;
+        }
+
+        let total: Duration = samples.iter().sum();
+        timings.push(Timing {
+            day: task.day,
+            part: task.part.number(),
+            iterations,
+            mean_ms: millis(total / iterations),
+            min_ms: millis(*samples.iter().min().unwrap()),
+            max_ms: millis(*samples.iter().max().unwrap()),
+        });
+    }
+
+    match selection.format {
+        Format::Text => {
+            println!(
+                "{:<16} {:>12} {:>12} {:>12}",
+                "", "mean (ms)", "min (ms)", "max (ms)"
+            );
+            for timing in &timings {
+                println!(
+                    "{:<16} {:>12.3} {:>12.3} {:>12.3}",
+                    format!("Day {} - Part {}", timing.day, timing.part),
+                    timing.mean_ms,
+                    timing.min_ms,
+                    timing.max_ms
+                );
+            }
+            for outcome in &skipped {
+                eprintln!("{}: skipped, no input", outcome.label());
+            }
+            for failure in &failures {
+                eprintln!(
+                    "{}: FAILED: {}",
+                    failure.label(),
+                    failure.error.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        Format::Json => print_json(&serde_json::json!({
+            "timings": timings,
+            "failures": failures,
+            "skipped": skipped,
+        })),
+    }
+
+    failures.is_empty()
+}
+
+#[derive(Serialize)]
+struct Listing {
+    day: u8,
+    title: &'static str,
+    variants: Vec<String>,
+}
+
+fn list(format: Format) -> bool {
+    let listings: Vec<Listing> = solution::registry()
+        .iter()
+        .map(|puzzle| Listing {
+            day: puzzle.day,
+            title: puzzle.title,
+            variants: runner::variants(puzzle.day)
+                .into_iter()
+                .map(|v| v.name)
+                .collect(),
+        })
+        .collect();
+
+    match format {
+        Format::Text => {
+            for listing in &listings {
+                let variants = if listing.variants.is_empty() {
+                    "no inputs".to_string()
+                } else {
+                    listing.variants.join(", ")
+                };
+                println!("Day {:>2}: {} ({})", listing.day, listing.title, variants);
+            }
+        }
+        Format::Json => print_json(&listings),
+    }
+
+    true
+}
+
+#[derive(Serialize)]
+struct Check {
+    day: u8,
+    part: u8,
+    variant: String,
+    input: String,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actual: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Check {
+    fn new(task: &Task, verdict: Verdict) -> Self {
+        let mut check = Self {
+            day: task.day,
+            part: task.part.number(),
+            variant: task.variant.clone(),
+            input: answers::input_key(&task.input),
+            status: if verdict.is_failure() { "failed" } else { "ok" },
+            expected: None,
+            actual: None,
+            error: None,
+        };
+
+        match verdict {
+            Verdict::Correct => {}
+            Verdict::Incorrect { expected, actual } => {
+                check.status = "incorrect";
+                check.expected = Some(expected);
+                check.actual = Some(actual);
+            }
+            Verdict::Unrecorded(actual) => {
+                check.status = "unrecorded";
+                check.actual = Some(actual);
+            }
+            Verdict::Failed(e) => check.error = Some(e.to_string()),
+        }
+
+        check
+    }
+
+    /// A task left out because its input doesn't exist.
+    fn skipped(task: &Task) -> Self {
+        Self {
+            day: task.day,
+            part: task.part.number(),
+            variant: task.variant.clone(),
+            input: answers::input_key(&task.input),
+            status: "skipped",
+            expected: None,
+            actual: None,
+            error: None,
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(self.status, "failed" | "incorrect")
+    }
+}
+
+fn verify(selection: &Selection, answers_path: &Path) -> bool {
+    let answers = match Answers::load(answers_path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+
+    let checks: Vec<Check> = selection
+        .tasks()
+        .iter()
+        .map(|task| {
+            // an input that's gone missing still fails if there's an answer recorded for it
+            let recorded = answers
+                .expected(&answers::input_key(&task.input), task.part)
+                .is_some();
+            if selection.skips(&task.input) && !recorded {
+                Check::skipped(task)
+            } else {
+                Check::new(task, answers.verify(task.day, task.part, &task.input))
+            }
+        })
+        .collect();
+    let failed = checks.iter().filter(|c| c.is_failure()).count();
+    let skipped = checks.iter().filter(|c| c.status == "skipped").count();
+
+    match selection.format {
+        Format::Text => {
+            for check in &checks {
+                let label = label(check.day, check.part, &check.variant);
+                match check.status {
+                    "incorrect" => println!(
+                        "{}: INCORRECT: expected {:?}, got {:?}",
+                        label,
+                        check.expected.as_deref().unwrap_or_default(),
+                        check.actual.as_deref().unwrap_or_default()
+                    ),
+                    "unrecorded" => println!(
+                        "{}: no recorded answer, got {:?}",
+                        label,
+                        check.actual.as_deref().unwrap_or_default()
+                    ),
+                    "failed" => println!(
+                        "{}: FAILED: {}",
+                        label,
+                        check.error.as_deref().unwrap_or_default()
+                    ),
+                    "skipped" => println!("{}: skipped, no input", label),
+                    _ => println!("{}: ok", label),
+                }
+            }
+            let checked = checks.len() - skipped;
+            println!(
+                "{}/{} passed, {} skipped",
+                checked - failed,
+                checked,
+                skipped
+            );
+        }
+        Format::Json => print_json(&checks),
+    }
+
+    failed == 0
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let success = match &cli.command {
+        Command::Run {
+            selection,
+            report: true,
+        } => report(selection),
+        Command::Run { selection, .. } => run(selection),
+        Command::Bench {
+            selection,
+            iterations,
+        } => bench(selection, *iterations),
+        Command::List { format } => list(*format),
+        Command::Verify { selection, answers } => verify(selection, answers),
+    };
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
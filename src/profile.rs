@@ -0,0 +1,139 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    runner::{self, Part, RunError},
+    solution::Puzzle,
+};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and keeps track of how many bytes are live, and the most
+/// that have been live since the last [`reset_peak`].
+///
+/// It only sees allocations once a binary installs it with `#[global_allocator]`;
+/// otherwise every measurement reads as 0.
+pub struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let current = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                record_alloc(new_size - layout.size());
+            } else {
+                record_dealloc(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Bytes currently allocated through [`CountingAllocator`].
+pub fn allocated_bytes() -> usize {
+    ALLOCATED.load(Ordering::Relaxed)
+}
+
+/// Starts a new peak measurement from the current allocation level, which is returned.
+pub fn reset_peak() -> usize {
+    let current = allocated_bytes();
+    PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+pub fn peak_bytes() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+/// How long each stage of a day took, and the most memory it had allocated at once.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+    /// Peak bytes allocated above what was already live before parsing started.
+    pub peak_bytes: usize,
+    pub answer1: Option<String>,
+    pub answer2: Option<String>,
+}
+
+/// Parses `input` once and solves the requested parts, timing each stage separately.
+pub fn profile(puzzle: &Puzzle, input: &str, parts: &[Part]) -> Result<Profile, RunError> {
+    let mut profile = Profile::default();
+    let baseline = reset_peak();
+
+    let start = Instant::now();
+    let parsed = runner::catch_panic(|| puzzle.parse(input))??;
+    profile.parse = start.elapsed();
+
+    for &part in parts {
+        let start = Instant::now();
+        let answer = runner::catch_panic(|| puzzle.solve(&parsed, part))?;
+        let elapsed = start.elapsed();
+
+        match part {
+            Part::One => {
+                profile.part1 = Some(elapsed);
+                profile.answer1 = Some(answer);
+            }
+            Part::Two => {
+                profile.part2 = Some(elapsed);
+                profile.answer2 = Some(answer);
+            }
+        }
+    }
+
+    drop(parsed);
+    profile.peak_bytes = peak_bytes().saturating_sub(baseline);
+
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    #[test]
+    fn test_profile() {
+        let puzzle = solution::find(6).unwrap();
+        let profile = profile(puzzle, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", &[Part::Two]).unwrap();
+        assert_eq!(None, profile.answer1);
+        assert_eq!(Some("19".to_string()), profile.answer2);
+        assert!(profile.part1.is_none() && profile.part2.is_some());
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt,
+    fmt::Display,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Once,
+};
+
+use crate::{error::ParseError, solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    UnknownDay(u8),
+    Input(PathBuf, io::Error),
+    Parse(ParseError),
+    Panicked {
+        message: String,
+        /// `file:line:column` of the panic, when the hook saw one.
+        location: Option<String>,
+    },
+    InvalidAnswers(String),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::UnknownDay(day) => write!(f, "day {} is not implemented", day),
+            RunError::Input(path, e) => write!(f, "could not read {}: {}", path.display(), e),
+            RunError::Parse(e) => write!(f, "could not parse input: {}", e),
+            RunError::Panicked {
+                message,
+                location: Some(location),
+            } => write!(f, "solution panicked at {}: {}", location, message),
+            RunError::Panicked { message, .. } => write!(f, "solution panicked: {}", message),
+            RunError::InvalidAnswers(message) => write!(f, "invalid answers manifest: {}", message),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<ParseError> for RunError {
+    fn from(e: ParseError) -> Self {
+        RunError::Parse(e)
+    }
+}
+
+/// Name of the variant stored in plain `dayN.txt`, i.e. the real puzzle input.
+pub const REAL_VARIANT: &str = "real";
+
+/// A named input for a day: `dayN.txt` is the real input, `dayN_<name>.txt` is variant `<name>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+pub fn input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input/2022")
+}
+
+/// Default location of a day's puzzle input.
+pub fn input_path(day: u8) -> PathBuf {
+    variant_path(day, REAL_VARIANT)
+}
+
+pub fn variant_path(day: u8, name: &str) -> PathBuf {
+    let file_name = if name == REAL_VARIANT {
+        format!("day{}.txt", day)
+    } else {
+        format!("day{}_{}.txt", day, name)
+    };
+    input_dir().join(file_name)
+}
+
+/// Works out which variant of which day a file name refers to, if any.
+fn parse_variant_file_name(file_name: &str) -> Option<(u8, String)> {
+    let stem = file_name.strip_prefix("day")?.strip_suffix(".txt")?;
+    let (day, name) = match stem.split_once('_') {
+        Some((day, name)) if !name.is_empty() => (day, name),
+        Some(_) => return None,
+        None => (stem, REAL_VARIANT),
+    };
+
+    Some((day.parse().ok()?, name.to_string()))
+}
+
+/// Every input variant on disk for `day`, with the real input first and the rest by name.
+pub fn variants(day: u8) -> Vec<Variant> {
+    let entries = match fs::read_dir(input_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    let mut variants: Vec<Variant> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let (variant_day, name) = parse_variant_file_name(path.file_name()?.to_str()?)?;
+            (variant_day == day).then_some(Variant { name, path })
+        })
+        .collect();
+
+    variants
+        .sort_by(|a, b| (a.name != REAL_VARIANT, &a.name).cmp(&(b.name != REAL_VARIANT, &b.name)));
+    variants
+}
+
+pub fn read_input(path: &Path) -> Result<String, RunError> {
+    let input = fs::read_to_string(path).map_err(|e| RunError::Input(path.to_path_buf(), e))?;
+    Ok(input.trim_end_matches('\n').to_string())
+}
+
+/// Parses `input` and runs one part of a day's solution, returning the answer as text.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<String, RunError> {
+    let puzzle = solution::find(day).ok_or(RunError::UnknownDay(day))?;
+    let answer = catch_panic(|| {
+        puzzle
+            .parse(input)
+            .map(|parsed| puzzle.solve(&parsed, part))
+    })?;
+    Ok(answer?)
+}
+
+thread_local! {
+    /// Whether this thread is inside [`catch_panic`], whose panics are reported through
+    /// the returned error rather than printed.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Where the last panic caught on this thread happened.
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Wraps the panic hook so it stays quiet (but remembers the location) for panics that
+/// [`catch_panic`] is about to catch, and behaves as before for every other panic.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                let location = info.location().map(|location| location.to_string());
+                PANIC_LOCATION.set(location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs `f`, turning a panic into [`RunError::Panicked`] so that a single broken day
+/// doesn't take the rest of a run down with it.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, RunError> {
+    install_panic_hook();
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        RunError::Panicked {
+            message,
+            location: PANIC_LOCATION.take(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            "13",
+            solve(9, Part::One, "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_variant_file_name() {
+        assert_eq!(
+            Some((5, "real".to_string())),
+            parse_variant_file_name("day5.txt")
+        );
+        assert_eq!(
+            Some((5, "test".to_string())),
+            parse_variant_file_name("day5_test.txt")
+        );
+        assert_eq!(
+            Some((10, "real".to_string())),
+            parse_variant_file_name("day10.txt")
+        );
+        assert_eq!(None, parse_variant_file_name("day5_.txt"));
+        assert_eq!(None, parse_variant_file_name("answers.toml"));
+    }
+
+    #[test]
+    fn test_variants() {
+        let names: Vec<String> = variants(5).into_iter().map(|v| v.name).collect();
+        assert_eq!(vec!["real", "test"], names);
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert!(matches!(
+            solve(42, Part::One, ""),
+            Err(RunError::UnknownDay(42))
+        ));
+        assert!(matches!(
+            solve(2, Part::One, "A X\nB"),
+            Err(RunError::Parse(_))
+        ));
+        assert!(matches!(
+            solve(6, Part::One, "aaaa"),
+            Err(RunError::Panicked { .. })
+        ));
+    }
+
+    #[test]
+    fn test_panic_location() {
+        let error = catch_panic(|| panic!("oops")).unwrap_err();
+        let expected = format!("solution panicked at {}:{}:", file!(), line!() - 1);
+        assert!(error.to_string().starts_with(&expected), "{}", error);
+        assert!(error.to_string().ends_with(": oops"), "{}", error);
+    }
+}
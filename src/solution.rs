@@ -0,0 +1,169 @@
+use std::{any::Any, fmt::Display};
+
+use crate::error::ParseError;
+use crate::runner::Part;
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day21,
+    day22, day23, day24, day25, day3, day4, day5, day6, day7, day8, day9,
+};
+
+/// A single day's puzzle: how to parse its input and how to answer both parts.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Parsed: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(input: &Self::Parsed) -> Self::Answer1;
+    fn part2(input: &Self::Parsed) -> Self::Answer2;
+}
+
+/// Declares `pub struct $name` and implements [`Solution`] for it by forwarding to the
+/// calling module's `input_generator`, `part1` and `part2`, as in
+/// `solution!(Day8, 8, "Treetop Tree House", Map, usize, usize);`.
+macro_rules! solution {
+    ($name:ident, $day:literal, $title:literal, $parsed:ty, $answer1:ty, $answer2:ty) => {
+        pub struct $name;
+
+        impl $crate::solution::Solution for $name {
+            const DAY: u8 = $day;
+            const TITLE: &'static str = $title;
+
+            type Parsed = $parsed;
+            type Answer1 = $answer1;
+            type Answer2 = $answer2;
+
+            fn parse(input: &str) -> Result<Self::Parsed, $crate::error::ParseError> {
+                input_generator(input)
+            }
+
+            fn part1(input: &Self::Parsed) -> Self::Answer1 {
+                part1(input)
+            }
+
+            fn part2(input: &Self::Parsed) -> Self::Answer2 {
+                part2(input)
+            }
+        }
+    };
+}
+pub(crate) use solution;
+
+/// A day's parsed input, with its type erased so every day fits in the registry.
+pub struct Parsed(Box<dyn Any>);
+
+/// Registry entry for a [`Solution`], usable without knowing its types.
+pub struct Puzzle {
+    pub day: u8,
+    pub title: &'static str,
+    parse: fn(&str) -> Result<Parsed, ParseError>,
+    part1: fn(&Parsed) -> String,
+    part2: fn(&Parsed) -> String,
+}
+
+impl Puzzle {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            title: S::TITLE,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Panics if `parsed` came from a different day's `parse`.
+    pub fn solve(&self, parsed: &Parsed, part: Part) -> String {
+        match part {
+            Part::One => (self.part1)(parsed),
+            Part::Two => (self.part2)(parsed),
+        }
+    }
+}
+
+fn parse<S: Solution>(input: &str) -> Result<Parsed, ParseError> {
+    S::parse(input).map(|parsed| Parsed(Box::new(parsed)))
+}
+
+fn downcast<S: Solution>(parsed: &Parsed) -> &S::Parsed {
+    parsed
+        .0
+        .downcast_ref()
+        .unwrap_or_else(|| panic!("input was not parsed by day {}", S::DAY))
+}
+
+fn part1<S: Solution>(parsed: &Parsed) -> String {
+    S::part1(downcast::<S>(parsed)).to_string()
+}
+
+fn part2<S: Solution>(parsed: &Parsed) -> String {
+    S::part2(downcast::<S>(parsed)).to_string()
+}
+
+static PUZZLES: [Puzzle; 25] = [
+    Puzzle::of::<day1::Day1>(),
+    Puzzle::of::<day2::Day2>(),
+    Puzzle::of::<day3::Day3>(),
+    Puzzle::of::<day4::Day4>(),
+    Puzzle::of::<day5::Day5>(),
+    Puzzle::of::<day6::Day6>(),
+    Puzzle::of::<day7::Day7>(),
+    Puzzle::of::<day8::Day8>(),
+    Puzzle::of::<day9::Day9>(),
+    Puzzle::of::<day10::Day10>(),
+    Puzzle::of::<day11::Day11>(),
+    Puzzle::of::<day12::Day12>(),
+    Puzzle::of::<day13::Day13>(),
+    Puzzle::of::<day14::Day14>(),
+    Puzzle::of::<day15::Day15>(),
+    Puzzle::of::<day16::Day16>(),
+    Puzzle::of::<day17::Day17>(),
+    Puzzle::of::<day18::Day18>(),
+    Puzzle::of::<day19::Day19>(),
+    Puzzle::of::<day20::Day20>(),
+    Puzzle::of::<day21::Day21>(),
+    Puzzle::of::<day22::Day22>(),
+    Puzzle::of::<day23::Day23>(),
+    Puzzle::of::<day24::Day24>(),
+    Puzzle::of::<day25::Day25>(),
+];
+
+/// Every implemented day, in calendar order.
+pub fn registry() -> &'static [Puzzle] {
+    &PUZZLES
+}
+
+pub fn find(day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_in_order() {
+        let days: Vec<u8> = registry().iter().map(|puzzle| puzzle.day).collect();
+        assert_eq!((1..=25).collect::<Vec<u8>>(), days);
+    }
+
+    #[test]
+    fn test_solution_trait() {
+        let parsed = day6::Day6::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        assert_eq!(7, day6::Day6::part1(&parsed));
+    }
+
+    #[test]
+    #[should_panic(expected = "input was not parsed by day 2")]
+    fn test_solve_with_other_days_input() {
+        let parsed = find(1).unwrap().parse("1000").unwrap();
+        find(2).unwrap().solve(&parsed, Part::One);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+/// `(x, y, z)` of a unit cube.
+pub type Voxel = (i32, i32, i32);
+
+/// Offsets to the six cubes sharing a face with a cube.
+pub const FACES: [Voxel; 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// The six cubes sharing a face with `voxel`.
+pub fn face_neighbors((x, y, z): Voxel) -> impl Iterator<Item = Voxel> {
+    FACES
+        .into_iter()
+        .map(move |(dx, dy, dz)| (x + dx, y + dy, z + dz))
+}
+
+/// A sparse set of unit cubes in unbounded 3D space.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoxelSet {
+    voxels: HashSet<Voxel>,
+}
+
+impl VoxelSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the voxel was newly added.
+    pub fn insert(&mut self, voxel: Voxel) -> bool {
+        self.voxels.insert(voxel)
+    }
+
+    pub fn contains(&self, voxel: Voxel) -> bool {
+        self.voxels.contains(&voxel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Voxel> + '_ {
+        self.voxels.iter().copied()
+    }
+
+    /// Smallest and largest corner of the box around every voxel, or `None` if empty.
+    pub fn bounds(&self) -> Option<(Voxel, Voxel)> {
+        let mut voxels = self.iter();
+        let first = voxels.next()?;
+
+        Some(voxels.fold((first, first), |(min, max), (x, y, z)| {
+            (
+                (min.0.min(x), min.1.min(y), min.2.min(z)),
+                (max.0.max(x), max.1.max(y), max.2.max(z)),
+            )
+        }))
+    }
+
+    /// Faces of voxels in the set that don't touch another voxel in the set.
+    pub fn surface_area(&self) -> usize {
+        self.iter()
+            .flat_map(face_neighbors)
+            .filter(|&neighbor| !self.contains(neighbor))
+            .count()
+    }
+}
+
+impl FromIterator<Voxel> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Voxel>>(iter: I) -> Self {
+        Self {
+            voxels: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surface_area() {
+        let mut set = VoxelSet::new();
+        assert_eq!(0, set.surface_area());
+        assert!(set.insert((1, 1, 1)));
+        assert_eq!(6, set.surface_area());
+        assert!(set.insert((2, 1, 1)));
+        assert!(!set.insert((2, 1, 1)));
+        assert_eq!(10, set.surface_area());
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(None, VoxelSet::new().bounds());
+
+        let set: VoxelSet = [(1, -2, 3), (-4, 5, 0)].into_iter().collect();
+        assert_eq!(Some(((-4, -2, 0), (1, 5, 3))), set.bounds());
+    }
+}
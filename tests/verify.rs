@@ -0,0 +1,39 @@
+use advent_of_code_2022::{
+    answers::{answers_path, Answers},
+    runner::{input_dir, variants, Part},
+    solution::registry,
+};
+
+#[test]
+fn test_inputs_match_answers() {
+    let answers = Answers::load(&answers_path()).unwrap();
+    let mut failures = vec![];
+
+    for day in registry().iter().map(|puzzle| puzzle.day) {
+        for variant in variants(day) {
+            for part in Part::ALL {
+                match answers.verify(day, part, &variant.path) {
+                    verdict if !verdict.is_failure() => {}
+                    verdict => failures.push(format!(
+                        "day {} part {} ({}): {:?}",
+                        day, part, variant.name, verdict
+                    )),
+                }
+            }
+        }
+    }
+
+    // every recorded answer needs its input, or a deleted file would go unnoticed
+    for key in answers.keys() {
+        let path = input_dir().join(format!("{}.txt", key));
+        if !path.exists() {
+            failures.push(format!(
+                "{}: answers recorded but {} is missing",
+                key,
+                path.display()
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}